@@ -0,0 +1,356 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{IterationStep, TransportError, TransportPlan, TransportProblem, cost_change};
+
+// Экранирование текста для вставки в HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Разбор строки CSV в числа; номер строки нужен для сообщения об ошибке
+fn parse_fields(line_no: usize, fields: &[&str]) -> Result<Vec<i32>, TransportError> {
+    fields
+        .iter()
+        .map(|field| {
+            field.trim().parse().map_err(|_| TransportError::Parse {
+                line: line_no,
+                message: format!("не число: \"{}\"", field.trim()),
+            })
+        })
+        .collect()
+}
+
+impl TransportProblem {
+    // Граф перевозок в формате GraphViz DOT: поставщики слева, потребители
+    // справа, толщина ребра пропорциональна объему перевозки
+    pub fn plan_to_dot(&self, plan: &TransportPlan) -> String {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let max_allocation = plan
+            .allocations
+            .iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut dot = String::from("digraph transport {\n    rankdir=LR;\n");
+
+        for i in 0..m {
+            dot.push_str(&format!(
+                "    A{} [shape=box, label=\"{}\\n{}\"];\n",
+                i + 1,
+                self.supplier_label(i).replace('"', "\\\""),
+                self.supplies[i]
+            ));
+        }
+        for j in 0..n {
+            dot.push_str(&format!(
+                "    B{} [shape=ellipse, label=\"{}\\n{}\"];\n",
+                j + 1,
+                self.consumer_label(j).replace('"', "\\\""),
+                self.demands[j]
+            ));
+        }
+
+        for i in 0..m {
+            for j in 0..n {
+                let allocation = plan.allocations[i][j];
+                if allocation > 0 {
+                    let penwidth = 1.0 + 4.0 * allocation as f64 / max_allocation as f64;
+                    dot.push_str(&format!(
+                        "    A{} -> B{} [label=\"{} ({})\", penwidth={:.1}];\n",
+                        i + 1,
+                        j + 1,
+                        allocation,
+                        self.costs[i][j],
+                        penwidth
+                    ));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    // Самодостаточная HTML-страница с таблицей плана: занятые клетки
+    // подсвечены, по краям — запасы и потребности, внизу общая стоимость
+    pub fn to_html(&self, plan: &TransportPlan) -> String {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Транспортная задача</title>\n<style>\n\
+             table { border-collapse: collapse; font-family: sans-serif; }\n\
+             th, td { border: 1px solid #999; padding: 4px 8px; text-align: right; }\n\
+             th { background: #eee; }\n\
+             td.used { background: #cfe8cf; font-weight: bold; }\n\
+             td.forbidden { color: #aaa; }\n\
+             .cost { color: #666; font-size: smaller; }\n\
+             </style>\n</head>\n<body>\n<table>\n",
+        );
+
+        html.push_str("<tr><th></th>");
+        for j in 0..n {
+            html.push_str(&format!(
+                "<th>{}</th>",
+                escape_html(&self.consumer_label(j))
+            ));
+        }
+        html.push_str("<th>Запасы</th></tr>\n");
+
+        for i in 0..m {
+            html.push_str(&format!(
+                "<tr><th>{}</th>",
+                escape_html(&self.supplier_label(i))
+            ));
+            for j in 0..n {
+                let allocation = plan.allocations[i][j];
+                if allocation > 0 {
+                    html.push_str(&format!(
+                        "<td class=\"cell used\">{} <span class=\"cost\">({})</span></td>",
+                        allocation, self.costs[i][j]
+                    ));
+                } else if self.is_forbidden(i, j) {
+                    html.push_str("<td class=\"cell forbidden\">x</td>");
+                } else {
+                    html.push_str(&format!(
+                        "<td class=\"cell\">- <span class=\"cost\">({})</span></td>",
+                        self.costs[i][j]
+                    ));
+                }
+            }
+            html.push_str(&format!(
+                "<td class=\"margin\">{}/{}</td></tr>\n",
+                plan.allocations.row_sum(i),
+                self.supplies[i]
+            ));
+        }
+
+        html.push_str("<tr><th>Потребности</th>");
+        for j in 0..n {
+            html.push_str(&format!(
+                "<td class=\"margin\">{}/{}</td>",
+                plan.allocations.col_sum(j),
+                self.demands[j]
+            ));
+        }
+        html.push_str("<td></td></tr>\n</table>\n");
+
+        html.push_str(&format!(
+            "<p>Общая стоимость: {} {}</p>\n</body>\n</html>\n",
+            plan.total_cost,
+            escape_html(&self.unit)
+        ));
+        html
+    }
+
+    // Задача как линейная программа в формате CPLEX LP для проверки внешним
+    // решателем. Переменная x_i_j — перевозка от A{i} к B{j}, нумерация с 1;
+    // запрещенные маршруты остаются в целевой функции со стоимостью FORBIDDEN.
+    // Закрытая задача записывается равенствами; в открытой запасы
+    // ограничены сверху, потребности снизу, поэтому при нехватке запасов
+    // внешний решатель сообщит о недопустимости (здесь недостача покрывается
+    // фиктивным поставщиком). Фиксированные стоимости не записываются
+    pub fn to_lp_format(&self) -> String {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let (supply_sense, demand_sense) = if self.is_balanced() {
+            ("=", "=")
+        } else {
+            ("<=", ">=")
+        };
+
+        let mut lp = String::from("\\ transport_problem\nMinimize\n obj:");
+        for i in 0..m {
+            if i > 0 {
+                lp.push_str("\n    ");
+            }
+            for j in 0..n {
+                let sign = if i == 0 && j == 0 { "" } else { " +" };
+                lp.push_str(&format!(
+                    "{} {} x_{}_{}",
+                    sign,
+                    self.costs[i][j],
+                    i + 1,
+                    j + 1
+                ));
+            }
+        }
+
+        lp.push_str("\nSubject To\n");
+        for i in 0..m {
+            let terms: Vec<String> = (0..n).map(|j| format!("x_{}_{}", i + 1, j + 1)).collect();
+            lp.push_str(&format!(
+                " supply_{}: {} {} {}\n",
+                i + 1,
+                terms.join(" + "),
+                supply_sense,
+                self.supplies[i]
+            ));
+        }
+        for j in 0..n {
+            let terms: Vec<String> = (0..m).map(|i| format!("x_{}_{}", i + 1, j + 1)).collect();
+            lp.push_str(&format!(
+                " demand_{}: {} {} {}\n",
+                j + 1,
+                terms.join(" + "),
+                demand_sense,
+                self.demands[j]
+            ));
+        }
+
+        lp.push_str("Bounds\n");
+        for i in 0..m {
+            for j in 0..n {
+                lp.push_str(&format!(" x_{}_{} >= 0\n", i + 1, j + 1));
+            }
+        }
+        lp.push_str("End\n");
+        lp
+    }
+
+    // Чтение задачи из CSV: первая строка — пустая ячейка и потребности,
+    // далее в каждой строке запас поставщика и его стоимости
+    pub fn from_csv(text: &str) -> Result<TransportProblem, TransportError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (header_no, header) = lines.next().ok_or(TransportError::Parse {
+            line: 1,
+            message: "пустой файл".to_string(),
+        })?;
+        let header: Vec<&str> = header.split(',').collect();
+        let demands = parse_fields(header_no, &header[1..])?;
+
+        let mut supplies = Vec::new();
+        let mut costs = Vec::new();
+        for (line_no, line) in lines {
+            let fields: Vec<&str> = line.split(',').collect();
+            let values = parse_fields(line_no, &fields)?;
+            supplies.push(values[0]);
+            costs.push(values[1..].to_vec());
+        }
+
+        if demands.is_empty() || supplies.is_empty() {
+            return Err(TransportError::Parse {
+                line: header_no,
+                message: "нет потребителей или поставщиков".to_string(),
+            });
+        }
+
+        TransportProblem::try_new(supplies, demands, costs)
+    }
+
+    // Запись задачи в CSV в формате, который читает from_csv
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+
+        for demand in &self.demands {
+            csv.push_str(&format!(",{}", demand));
+        }
+        csv.push('\n');
+
+        for (supply, row) in self.supplies.iter().zip(&self.costs) {
+            csv.push_str(&supply.to_string());
+            for cost in row {
+                csv.push_str(&format!(",{}", cost));
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    // Траектория стоимости по истории поворотов в CSV для построения
+    // графика сходимости: строки «iteration,total_cost», итерация 0 —
+    // начальный план. Его стоимость восстанавливается по первому повороту
+    // (стоимость после него минус оценка, умноженная на объем), что точно
+    // для задач без фиксированных доплат. Пустая история — только заголовок
+    pub fn cost_trajectory_csv(&self, history: &[IterationStep]) -> String {
+        let mut csv = String::from("iteration,total_cost\n");
+
+        if let Some(first) = history.first() {
+            let initial = first.cost - cost_change(first.delta, first.amount);
+            csv.push_str(&format!("0,{}\n", initial));
+        }
+        for step in history {
+            csv.push_str(&format!("{},{}\n", step.iteration, step.cost));
+        }
+
+        csv
+    }
+
+    // Кадры для пошаговой анимации: план до первого поворота и после каждого;
+    // история должна начинаться с северо-западного угла, как в solve
+    pub fn history_to_frames(&self, history: &[IterationStep]) -> Vec<String> {
+        let problem = self.balanced();
+        let mut plan = problem.north_west_corner();
+        let mut frames = Vec::with_capacity(history.len() + 1);
+
+        let mut render = |plan: &TransportPlan| {
+            let mut buffer = Vec::new();
+            let _ = problem.write_plan(&mut buffer, plan);
+            frames.push(String::from_utf8_lossy(&buffer).into_owned());
+        };
+
+        render(&plan);
+        for step in history {
+            for (k, &(i, j)) in step.cycle.iter().enumerate() {
+                if k % 2 == 0 {
+                    plan.allocations[i][j] += step.amount;
+                } else {
+                    plan.allocations[i][j] -= step.amount;
+                }
+            }
+            plan.total_cost = step.cost;
+            render(&plan);
+        }
+
+        frames
+    }
+
+    // Решение всех задач *.csv из каталога; ошибка в одном файле не
+    // прерывает обработку остальных. Результаты упорядочены по имени файла
+    pub fn solve_directory<P: AsRef<Path>>(
+        dir: P,
+    ) -> Vec<(String, Result<TransportPlan, TransportError>)> {
+        let entries = match fs::read_dir(dir.as_ref()) {
+            Ok(entries) => entries,
+            Err(e) => {
+                let name = dir.as_ref().display().to_string();
+                return vec![(name, Err(TransportError::Io(e.to_string())))];
+            }
+        };
+
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "csv"))
+            .collect();
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let name = path
+                    .file_name()
+                    .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+                let result = fs::read_to_string(&path)
+                    .map_err(|e| TransportError::Io(e.to_string()))
+                    .and_then(|text| TransportProblem::from_csv(&text))
+                    .map(|problem| problem.solve_to_plan());
+                (name, result)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,4419 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::mpsc::Sender;
+use std::thread;
+
+mod export;
+mod matrix;
+
+pub use matrix::Matrix;
+
+// Единица стоимости по умолчанию (условные единицы)
+pub const DEFAULT_UNIT: &str = "у.е.";
+
+// Стоимость запрещенного маршрута (метод большого M)
+pub const FORBIDDEN: i32 = 1_000_000;
+
+// Предельное число итераций метода потенциалов
+pub const MAX_ITERATIONS: usize = 1000;
+
+// Допуск при сравнении оценок клеток с нулем
+const EPSILON: f64 = 1e-9;
+
+// Структура для представления транспортной задачи
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportProblem {
+    pub supplies: Vec<i32>,
+    pub demands: Vec<i32>,
+    pub costs: Matrix,
+    // Фиксированная доплата за использование маршрута (задача с фиксированными доплатами)
+    pub fixed_costs: Option<Matrix>,
+    pub unit: String,
+    // Названия поставщиков и потребителей для вывода вместо A1, B1
+    pub supplier_names: Option<Vec<String>>,
+    pub consumer_names: Option<Vec<String>>,
+    // Журнал оптимизации выводит каждую print_every-ю итерацию и итоговую
+    pub print_every: usize,
+    // Запись перевозок и стоимостей в таблицах и итогах
+    pub number_format: NumberFormat,
+    // Пропускная способность маршрутов; 0 — без ограничения.
+    // Учитывается только solve_capacitated
+    pub route_capacities: Option<Matrix>,
+}
+
+// Структура для хранения плана перевозок
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransportPlan {
+    pub allocations: Matrix,
+    pub total_cost: i32,
+}
+
+// Вывод плана без стоимостей клеток (план не хранит матрицу стоимостей)
+impl fmt::Display for TransportPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.allocations.cols();
+
+        write!(f, "    ")?;
+        for j in 0..n {
+            write!(f, "\tB{}", j + 1)?;
+        }
+        writeln!(f)?;
+
+        for (i, row) in self.allocations.iter().enumerate() {
+            write!(f, "A{}  ", i + 1)?;
+            for &allocation in row {
+                if allocation > 0 {
+                    write!(f, "\t{}", allocation)?;
+                } else {
+                    write!(f, "\t-")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        write!(f, "Общая стоимость: {} {}", self.total_cost, DEFAULT_UNIT)
+    }
+}
+
+// Отдельная перевозка плана: откуда, куда, объем и стоимость
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shipment {
+    pub from: usize,
+    pub to: usize,
+    pub amount: i32,
+    pub unit_cost: i32,
+    pub total: i32,
+}
+
+// Состояние оптимизации: текущий план, число выполненных поворотов,
+// признак того, что улучшающих клеток не осталось, и ошибка, прервавшая цикл
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    pub plan: TransportPlan,
+    pub iterations: usize,
+    pub converged: bool,
+    pub error: Option<TransportError>,
+}
+
+// Полное решение: оптимальный план сбалансированной задачи, потенциалы
+// оптимального базиса (u — строки, v — столбцы), значение двойственной
+// целевой функции и сведения о ходе оптимизации
+#[derive(Debug, Clone)]
+pub struct FullSolution {
+    pub plan: TransportPlan,
+    pub u: Vec<f64>,
+    pub v: Vec<f64>,
+    pub dual_objective: f64,
+    pub iterations: usize,
+    pub converged: bool,
+    // Есть другой план с той же стоимостью
+    pub alternative_optima: bool,
+}
+
+// Один поворот метода потенциалов
+#[derive(Debug, Clone, PartialEq)]
+pub struct IterationStep {
+    pub iteration: usize,
+    // Клетка, введенная в базис, и ее оценка
+    pub entering: (usize, usize),
+    pub delta: f64,
+    // Объем, перемещенный по циклу
+    pub amount: i32,
+    // Цикл пересчета, начиная с вводимой клетки; знаки чередуются
+    pub cycle: Vec<(usize, usize)>,
+    // Общая стоимость после поворота
+    pub cost: i32,
+}
+
+// Событие потокового решения solve_streaming
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveEvent {
+    InitialPlan(TransportPlan),
+    Pivot(IterationStep),
+    // Итоговый оптимальный план
+    Optimal(TransportPlan),
+}
+
+// Сравнение начального, оптимального и эталонных планов
+#[derive(Debug)]
+pub struct AnalysisReport {
+    pub initial_cost: i32,
+    // (наименьшая, наибольшая, размах) стоимость начальных планов всех методов
+    pub initial_spread: (i32, i32, i32),
+    pub optimal_cost: i32,
+    pub worst_cost: i32,
+    pub references: Vec<ReferencePlan>,
+    pub unit: String,
+}
+
+impl AnalysisReport {
+    // Экономия оптимального плана относительно начального
+    pub fn savings(&self) -> i32 {
+        self.initial_cost - self.optimal_cost
+    }
+
+    pub fn savings_percent(&self) -> f64 {
+        if self.initial_cost == 0 {
+            return 0.0;
+        }
+        (self.savings() as f64 / self.initial_cost as f64) * 100.0
+    }
+}
+
+impl fmt::Display for AnalysisReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Начальная стоимость: {} {}",
+            self.initial_cost, self.unit
+        )?;
+        let (low, high, range) = self.initial_spread;
+        writeln!(
+            f,
+            "Начальные методы: от {} до {} {} (разброс {})",
+            low, high, self.unit, range
+        )?;
+        for reference in &self.references {
+            if !reference.is_feasible() {
+                write!(f, "{}: недопустимый план:", reference.name)?;
+                for issue in &reference.issues {
+                    write!(f, " {};", issue)?;
+                }
+                writeln!(f)?;
+                continue;
+            }
+            let cost = reference.plan.total_cost;
+            writeln!(
+                f,
+                "{}: {} {} (отклонение от оптимума: {})",
+                reference.name,
+                cost,
+                self.unit,
+                cost - self.optimal_cost
+            )?;
+        }
+        writeln!(
+            f,
+            "Оптимальная стоимость: {} {}",
+            self.optimal_cost, self.unit
+        )?;
+        writeln!(f, "Наихудшая стоимость: {} {}", self.worst_cost, self.unit)?;
+        write!(
+            f,
+            "Экономия: {} {} ({:.1}%)",
+            self.savings(),
+            self.unit,
+            self.savings_percent()
+        )
+    }
+}
+
+// Эталонный план для сравнения с оптимумом; issues — нарушения
+// допустимости (размеры, баланс, знаки), пустой список — план допустим
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferencePlan {
+    pub name: String,
+    pub plan: TransportPlan,
+    pub issues: Vec<PlanIssue>,
+}
+
+impl ReferencePlan {
+    pub fn is_feasible(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+// Разбор ручного плана: насколько он дороже оптимума и какие перевозки
+// нужно изменить, чтобы получить оптимальный план
+#[derive(Debug)]
+pub struct GapReport {
+    pub manual_cost: i32,
+    pub optimal_cost: i32,
+    // (поставщик, потребитель, изменение объема): положительное — добавить
+    pub reallocations: Vec<(usize, usize, i32)>,
+    pub unit: String,
+}
+
+impl GapReport {
+    // Переплата ручного плана относительно оптимума
+    pub fn gap(&self) -> i32 {
+        self.manual_cost - self.optimal_cost
+    }
+
+    pub fn gap_percent(&self) -> f64 {
+        if self.optimal_cost == 0 {
+            return 0.0;
+        }
+        (self.gap() as f64 / self.optimal_cost as f64) * 100.0
+    }
+}
+
+impl fmt::Display for GapReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Стоимость ручного плана: {} {}",
+            self.manual_cost, self.unit
+        )?;
+        writeln!(
+            f,
+            "Оптимальная стоимость: {} {}",
+            self.optimal_cost, self.unit
+        )?;
+        write!(
+            f,
+            "Переплата: {} {} ({:.1}%)",
+            self.gap(),
+            self.unit,
+            self.gap_percent()
+        )?;
+        for &(i, j, change) in &self.reallocations {
+            if change > 0 {
+                write!(f, "\n  A{} -> B{}: добавить {}", i + 1, j + 1, change)?;
+            } else {
+                write!(f, "\n  A{} -> B{}: убрать {}", i + 1, j + 1, -change)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Правило выбора вводимой в базис клетки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotRule {
+    // Наименьшая (самая отрицательная) оценка — правило Данцига
+    #[default]
+    MostNegative,
+    // Первая клетка с отрицательной оценкой
+    FirstNegative,
+    // Наибольшее фактическое снижение стоимости: оценка × объем по циклу
+    LargestImprovement,
+}
+
+// Метод построения начального опорного плана
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InitialBasis {
+    #[default]
+    NorthWestCorner,
+    // Метод минимального элемента
+    LeastCost,
+    // Аппроксимация Фогеля
+    Vogel,
+    // Аппроксимация Рассела
+    Russell,
+}
+
+// Запись чисел при выводе: без разделителей или с группировкой разрядов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberFormat {
+    #[default]
+    Plain,
+    // 1 200 000
+    Spaces,
+    // 1,200,000
+    Commas,
+    // Разделители пробелами, если в таблице встречаются числа от 10 000
+    // (по cost_magnitude и объемам задачи), иначе без разделителей
+    Auto,
+}
+
+impl NumberFormat {
+    pub fn format(self, value: i32) -> String {
+        let separator = match self {
+            // Auto выбирается по задаче в resolved_number_format; сам по себе
+            // записывается как Plain
+            NumberFormat::Plain | NumberFormat::Auto => return value.to_string(),
+            NumberFormat::Spaces => ' ',
+            NumberFormat::Commas => ',',
+        };
+
+        let digits = value.unsigned_abs().to_string();
+        let mut grouped = String::new();
+        if value < 0 {
+            grouped.push('-');
+        }
+        for (k, digit) in digits.chars().enumerate() {
+            if k > 0 && (digits.len() - k).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+}
+
+// Вид таблицы плана при выводе
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    // Одна таблица с ячейками вида «перевозка(стоимость)»
+    #[default]
+    Combined,
+    // Две выровненные таблицы: сначала перевозки, затем стоимости
+    Split,
+}
+
+// Ошибки построения и решения задачи
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransportError {
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+    },
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    Parse {
+        line: usize,
+        message: String,
+    },
+    IndexOutOfRange {
+        index: usize,
+        len: usize,
+    },
+    Io(String),
+    NegativeAllocation {
+        cells: Vec<(usize, usize)>,
+    },
+    Infeasible,
+    // Нет ни одного поставщика или потребителя
+    Empty,
+    // Суммарные запасы или потребности не положительны
+    ZeroTotal,
+    // Перевозка по запрещенному маршруту
+    ForbiddenRouteUsed {
+        i: usize,
+        j: usize,
+    },
+    // Заданные клетки базиса содержат цикл или не связывают все строки и столбцы
+    BasisNotTree,
+    // Отрицательная стоимость маршрута (вероятно, прибыль вместо затрат)
+    NegativeCost {
+        i: usize,
+        j: usize,
+    },
+    // Метод потенциалов не сошелся за MAX_ITERATIONS ни от одного начального плана
+    NotConverged,
+    // Начальный план вырожден: до m + n - 1 базисных клеток не хватает missing
+    Degenerate {
+        missing: i32,
+    },
+    // Потенциалы не определены даже после достройки базиса
+    Potentials(PotentialError),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransportError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Несовпадение размерности: ожидалось {}, получено {}",
+                expected, found
+            ),
+            TransportError::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Строка стоимостей A{} имеет длину {}, ожидалось {}",
+                row + 1,
+                found,
+                expected
+            ),
+            TransportError::Parse { line, message } => {
+                write!(f, "Ошибка разбора в строке {}: {}", line, message)
+            }
+            TransportError::IndexOutOfRange { index, len } => {
+                write!(f, "Индекс {} вне диапазона 0..{}", index, len)
+            }
+            TransportError::Io(message) => write!(f, "Ошибка ввода-вывода: {}", message),
+            TransportError::NegativeAllocation { cells } => {
+                write!(f, "Отрицательные перевозки в клетках:")?;
+                for (i, j) in cells {
+                    write!(f, " ({}, {})", i + 1, j + 1)?;
+                }
+                Ok(())
+            }
+            TransportError::Infeasible => write!(f, "Допустимого плана не существует"),
+            TransportError::Empty => write!(f, "Нет поставщиков или потребителей"),
+            TransportError::ForbiddenRouteUsed { i, j } => write!(
+                f,
+                "Перевозка по запрещенному маршруту A{} -> B{}",
+                i + 1,
+                j + 1
+            ),
+            TransportError::ZeroTotal => {
+                write!(f, "Суммарные запасы и потребности должны быть положительны")
+            }
+            TransportError::BasisNotTree => write!(
+                f,
+                "Базисные клетки образуют цикл или не связывают все строки и столбцы"
+            ),
+            TransportError::Degenerate { missing } => write!(
+                f,
+                "Начальный план вырожденный: не хватает базисных клеток: {}",
+                missing
+            ),
+            TransportError::NotConverged => write!(
+                f,
+                "Метод потенциалов не сошелся за {} итераций",
+                MAX_ITERATIONS
+            ),
+            TransportError::NegativeCost { i, j } => write!(
+                f,
+                "Отрицательная стоимость маршрута A{} -> B{}",
+                i + 1,
+                j + 1
+            ),
+            TransportError::Potentials(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+// Ошибка вычисления потенциалов: базис не связывает все строки и столбцы
+#[derive(Debug, Clone, PartialEq)]
+pub enum PotentialError {
+    Disconnected {
+        missing_rows: Vec<usize>,
+        missing_cols: Vec<usize>,
+    },
+}
+
+impl fmt::Display for PotentialError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PotentialError::Disconnected {
+                missing_rows,
+                missing_cols,
+            } => write!(
+                f,
+                "Базис несвязный: не определены потенциалы строк {:?} и столбцов {:?}",
+                missing_rows, missing_cols
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PotentialError {}
+
+impl From<PotentialError> for TransportError {
+    fn from(error: PotentialError) -> Self {
+        TransportError::Potentials(error)
+    }
+}
+
+// Нарушения, найденные при проверке плана
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanIssue {
+    Dimensions {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    RowSum {
+        row: usize,
+        expected: i32,
+        found: i32,
+    },
+    ColumnSum {
+        column: usize,
+        expected: i32,
+        found: i32,
+    },
+    Negative {
+        i: usize,
+        j: usize,
+        value: i32,
+    },
+    // Занятых клеток больше m + n - 1: решение не базисное, есть цикл
+    TooManyRoutes {
+        expected: usize,
+        found: usize,
+    },
+    // Занятых клеток меньше m + n - 1: вырожденный план
+    Degenerate {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for PlanIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlanIssue::Dimensions { expected, found } => write!(
+                f,
+                "Размер плана {}x{}, ожидалось {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            PlanIssue::RowSum {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Поставщик A{} отгружает {}, запас {}",
+                row + 1,
+                found,
+                expected
+            ),
+            PlanIssue::ColumnSum {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Потребитель B{} получает {}, потребность {}",
+                column + 1,
+                found,
+                expected
+            ),
+            PlanIssue::Negative { i, j, value } => write!(
+                f,
+                "Отрицательная перевозка {} в клетке ({}, {})",
+                value,
+                i + 1,
+                j + 1
+            ),
+            PlanIssue::TooManyRoutes { expected, found } => write!(
+                f,
+                "Занято {} клеток при базисе {}: план не базисный",
+                found, expected
+            ),
+            PlanIssue::Degenerate { expected, found } => write!(
+                f,
+                "Занято {} клеток при базисе {}: план вырожденный",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl TransportProblem {
+    pub fn new() -> Self {
+        TransportProblem {
+            supplies: vec![200, 150, 150],
+            demands: vec![90, 100, 70, 130, 110],
+            costs: Matrix::try_from(vec![
+                vec![12, 15, 21, 14, 17],
+                vec![14, 8, 15, 11, 21],
+                vec![19, 16, 26, 12, 20],
+            ])
+            .unwrap(),
+            fixed_costs: None,
+            unit: DEFAULT_UNIT.to_string(),
+            supplier_names: None,
+            consumer_names: None,
+            print_every: 1,
+            number_format: NumberFormat::Plain,
+            route_capacities: None,
+        }
+    }
+
+    // Построение задачи с проверкой непустоты, положительности итогов
+    // и размерностей матрицы стоимостей
+    pub fn try_new(
+        supplies: Vec<i32>,
+        demands: Vec<i32>,
+        costs: Vec<Vec<i32>>,
+    ) -> Result<Self, TransportError> {
+        if supplies.is_empty() || demands.is_empty() {
+            return Err(TransportError::Empty);
+        }
+        if supplies.iter().sum::<i32>() <= 0 || demands.iter().sum::<i32>() <= 0 {
+            return Err(TransportError::ZeroTotal);
+        }
+        if costs.len() != supplies.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: supplies.len(),
+                found: costs.len(),
+            });
+        }
+        for (i, row) in costs.iter().enumerate() {
+            if row.len() != demands.len() {
+                return Err(TransportError::RaggedRow {
+                    row: i,
+                    expected: demands.len(),
+                    found: row.len(),
+                });
+            }
+        }
+
+        Ok(TransportProblem {
+            supplies,
+            demands,
+            costs: Matrix::try_from(costs)?,
+            fixed_costs: None,
+            unit: DEFAULT_UNIT.to_string(),
+            supplier_names: None,
+            consumer_names: None,
+            print_every: 1,
+            number_format: NumberFormat::Plain,
+            route_capacities: None,
+        })
+    }
+
+    // Построение задачи из неполной матрицы: короткие строки дополняются
+    // запрещенными маршрутами, возвращаются индексы дополненных строк
+    pub fn from_ragged(
+        supplies: Vec<i32>,
+        demands: Vec<i32>,
+        mut costs: Vec<Vec<i32>>,
+    ) -> Result<(Self, Vec<usize>), TransportError> {
+        let n = demands.len();
+        let mut padded = Vec::new();
+
+        for (i, row) in costs.iter_mut().enumerate() {
+            if row.len() < n {
+                row.resize(n, FORBIDDEN);
+                padded.push(i);
+            }
+        }
+
+        let problem = TransportProblem::try_new(supplies, demands, costs)?;
+        Ok((problem, padded))
+    }
+
+    // Закрытая задача с заданным общим объемом, разделенным между
+    // поставщиками и потребителями пропорционально долям. Доли округляются
+    // вниз, остаток от округления достается самой большой доле. Доли должны
+    // быть неотрицательными конечными числами с положительной суммой
+    pub fn from_ratios(
+        total: i32,
+        supply_ratios: &[f64],
+        demand_ratios: &[f64],
+        costs: Vec<Vec<i32>>,
+    ) -> Result<Self, TransportError> {
+        if supply_ratios.is_empty() || demand_ratios.is_empty() {
+            return Err(TransportError::Empty);
+        }
+        if total <= 0 {
+            return Err(TransportError::ZeroTotal);
+        }
+        let supplies = split_by_ratios(total, supply_ratios).ok_or(TransportError::ZeroTotal)?;
+        let demands = split_by_ratios(total, demand_ratios).ok_or(TransportError::ZeroTotal)?;
+
+        TransportProblem::try_new(supplies, demands, costs)
+    }
+
+    // Задание фиксированных доплат за маршруты; размер как у матрицы стоимостей
+    pub fn with_fixed_costs(mut self, fixed_costs: Vec<Vec<i32>>) -> Result<Self, TransportError> {
+        let fixed = Matrix::try_from(fixed_costs)?;
+        if fixed.rows() != self.costs.rows() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.costs.rows(),
+                found: fixed.rows(),
+            });
+        }
+        if fixed.cols() != self.costs.cols() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.costs.cols(),
+                found: fixed.cols(),
+            });
+        }
+        self.fixed_costs = Some(fixed);
+        Ok(self)
+    }
+
+    // Задание пропускной способности маршрутов; размер как у матрицы
+    // стоимостей, 0 — маршрут без ограничения
+    pub fn with_route_capacities(
+        mut self,
+        capacities: Vec<Vec<i32>>,
+    ) -> Result<Self, TransportError> {
+        let capacities = Matrix::try_from(capacities)?;
+        if capacities.rows() != self.costs.rows() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.costs.rows(),
+                found: capacities.rows(),
+            });
+        }
+        if capacities.cols() != self.costs.cols() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.costs.cols(),
+                found: capacities.cols(),
+            });
+        }
+        self.route_capacities = Some(capacities);
+        Ok(self)
+    }
+
+    // Строгая проверка стоимостей для цепочки после try_new: задачи с
+    // отрицательными стоимостями отклоняются (без нее solve только предупреждает)
+    pub fn reject_negative_costs(self) -> Result<Self, TransportError> {
+        match self.has_negative_costs() {
+            Some((i, j)) => Err(TransportError::NegativeCost { i, j }),
+            None => Ok(self),
+        }
+    }
+
+    // Задание названий поставщиков и потребителей; их число должно совпадать
+    // с числом запасов и потребностей
+    pub fn with_names(
+        mut self,
+        supplier_names: Vec<String>,
+        consumer_names: Vec<String>,
+    ) -> Result<Self, TransportError> {
+        if supplier_names.len() != self.supplies.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.supplies.len(),
+                found: supplier_names.len(),
+            });
+        }
+        if consumer_names.len() != self.demands.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.demands.len(),
+                found: consumer_names.len(),
+            });
+        }
+        self.supplier_names = Some(supplier_names);
+        self.consumer_names = Some(consumer_names);
+        Ok(self)
+    }
+
+    // Подпись поставщика: название или A{i}; фиктивный поставщик
+    // сбалансированной задачи названия не имеет
+    pub fn supplier_label(&self, i: usize) -> String {
+        self.supplier_names
+            .as_ref()
+            .and_then(|names| names.get(i))
+            .cloned()
+            .unwrap_or_else(|| format!("A{}", i + 1))
+    }
+
+    // Подпись потребителя: название или B{j}
+    pub fn consumer_label(&self, j: usize) -> String {
+        self.consumer_names
+            .as_ref()
+            .and_then(|names| names.get(j))
+            .cloned()
+            .unwrap_or_else(|| format!("B{}", j + 1))
+    }
+
+    // Добавление потребителя со столбцом стоимостей от каждого поставщика;
+    // после этого задачу можно решить заново
+    pub fn add_consumer(
+        &mut self,
+        demand: i32,
+        costs_from_suppliers: Vec<i32>,
+    ) -> Result<(), TransportError> {
+        if costs_from_suppliers.len() != self.supplies.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.supplies.len(),
+                found: costs_from_suppliers.len(),
+            });
+        }
+
+        self.demands.push(demand);
+        self.costs.push_col(costs_from_suppliers);
+        if let Some(fixed) = &mut self.fixed_costs {
+            fixed.push_col(vec![0; self.supplies.len()]);
+        }
+        if let Some(capacities) = &mut self.route_capacities {
+            capacities.push_col(vec![0; self.supplies.len()]);
+        }
+        Ok(())
+    }
+
+    // Частота вывода итераций оптимизации; 0 считается за 1
+    pub fn with_print_every(mut self, print_every: usize) -> Self {
+        self.print_every = print_every.max(1);
+        self
+    }
+
+    // Первая в построчном порядке клетка с отрицательной стоимостью.
+    // Эвристики и метод потенциалов рассчитаны на затраты; отрицательные
+    // значения обычно означают, что вместо затрат передана прибыль
+    pub fn has_negative_costs(&self) -> Option<(usize, usize)> {
+        self.costs
+            .iter()
+            .enumerate()
+            .find_map(|(i, row)| row.iter().position(|&cost| cost < 0).map(|j| (i, j)))
+    }
+
+    pub fn is_forbidden(&self, i: usize, j: usize) -> bool {
+        self.costs[i][j] >= FORBIDDEN
+    }
+
+    // Группировка разрядов в таблицах плана и итоговых стоимостях
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    // Формат чисел для вывода: Auto заменяется на Spaces, если наибольшая
+    // разрешенная стоимость, запас или потребность не меньше 10 000
+    pub fn resolved_number_format(&self) -> NumberFormat {
+        if self.number_format != NumberFormat::Auto {
+            return self.number_format;
+        }
+        let largest = self
+            .supplies
+            .iter()
+            .chain(&self.demands)
+            .copied()
+            .chain([self.cost_magnitude().1])
+            .max()
+            .unwrap_or(0);
+        if largest >= 10_000 {
+            NumberFormat::Spaces
+        } else {
+            NumberFormat::Plain
+        }
+    }
+
+    // Задание единицы стоимости для вывода ("$", "€", "руб.")
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = unit.to_string();
+        self
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        let total_supply: i32 = self.supplies.iter().sum();
+        let total_demand: i32 = self.demands.iter().sum();
+        total_supply == total_demand
+    }
+
+    // Задача о назначениях: квадратная матрица, все запасы и потребности равны 1
+    pub fn is_assignment(&self) -> bool {
+        self.supplies.len() == self.demands.len()
+            && self.supplies.iter().all(|&s| s == 1)
+            && self.demands.iter().all(|&d| d == 1)
+    }
+
+    // Приведение к закрытой задаче добавлением фиктивного поставщика
+    // или потребителя с нулевыми стоимостями
+    pub fn balanced(&self) -> TransportProblem {
+        let total_supply: i32 = self.supplies.iter().sum();
+        let total_demand: i32 = self.demands.iter().sum();
+        let mut problem = self.clone();
+
+        if total_supply > total_demand {
+            problem.demands.push(total_supply - total_demand);
+            problem.costs.push_col(vec![0; self.supplies.len()]);
+            if let Some(fixed) = &mut problem.fixed_costs {
+                fixed.push_col(vec![0; self.supplies.len()]);
+            }
+            if let Some(capacities) = &mut problem.route_capacities {
+                capacities.push_col(vec![0; self.supplies.len()]);
+            }
+        } else if total_demand > total_supply {
+            problem.supplies.push(total_demand - total_supply);
+            problem.costs.push_row(vec![0; self.demands.len()]);
+            if let Some(fixed) = &mut problem.fixed_costs {
+                fixed.push_row(vec![0; self.demands.len()]);
+            }
+            if let Some(capacities) = &mut problem.route_capacities {
+                capacities.push_row(vec![0; self.demands.len()]);
+            }
+        }
+
+        problem
+    }
+
+    // Решение одним вызовом: план, потенциалы и признаки сходимости и
+    // альтернативного оптимума. Альтернативный оптимум засчитывается, если
+    // поворот через небазисную клетку с нулевой оценкой сдвигает груз.
+    // Ошибка Infeasible, если оптимум не обходится без запрещенных маршрутов
+    pub fn solve_full(&self) -> Result<FullSolution, TransportError> {
+        let problem = self.balanced();
+        let start = problem.north_west_corner();
+        let result = problem.optimize_with_callback(start, |_| true);
+        let plan = result.plan;
+
+        if plan
+            .occupied_cells()
+            .any(|(i, j, _)| problem.is_forbidden(i, j))
+        {
+            return Err(TransportError::Infeasible);
+        }
+
+        let mut basis = problem.optimal_basis(&plan.allocations);
+        let (u, v) = problem.repaired_potentials(&mut basis, &plan.allocations)?;
+
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+        let mut alternative_optima = false;
+        for i in 0..m {
+            for j in 0..n {
+                let delta = problem.reduced_cost(i, j, &u, &v);
+                if basis[i][j] || problem.is_forbidden(i, j) || delta.abs() >= EPSILON {
+                    continue;
+                }
+                let mut next = plan.allocations.clone();
+                let mut next_basis = basis.clone();
+                if problem
+                    .pivot_in_basis(&mut next, &mut next_basis, i, j)
+                    .is_some_and(|amount| amount > 0)
+                {
+                    alternative_optima = true;
+                }
+            }
+        }
+
+        let dual_objective = problem.dual_objective(&u, &v);
+
+        Ok(FullSolution {
+            plan,
+            u,
+            v,
+            dual_objective,
+            iterations: result.iterations,
+            converged: result.converged,
+            alternative_optima,
+        })
+    }
+
+    // Решение с повторными попытками: если от плана северо-западного угла
+    // оптимизация не сходится за MAX_ITERATIONS (например, зацикливается на
+    // вырожденном базисе), она повторяется от планов Фогеля и минимального
+    // элемента. Возвращается первый сошедшийся результат
+    pub fn robust_solve(&self) -> Result<TransportPlan, TransportError> {
+        self.robust_solve_with_callback(|_, _| true)
+    }
+
+    // robust_solve с уведомлением после каждого поворота; false прерывает
+    // попытку от текущего начального плана, и решение идет к следующему
+    pub fn robust_solve_with_callback<F: FnMut(InitialBasis, &OptimizationResult) -> bool>(
+        &self,
+        mut callback: F,
+    ) -> Result<TransportPlan, TransportError> {
+        let problem = self.balanced();
+        for method in [
+            InitialBasis::NorthWestCorner,
+            InitialBasis::Vogel,
+            InitialBasis::LeastCost,
+        ] {
+            let start = problem.initial_plan(method);
+            let result =
+                problem.optimize_with_callback(start, |progress| callback(method, progress));
+            if result.converged {
+                return Ok(result.plan);
+            }
+        }
+        Err(TransportError::NotConverged)
+    }
+
+    // Решение с учетом пропускной способности маршрутов. Каждый
+    // ограниченный маршрут (i, j) заменяется промежуточным узлом k: столбец k
+    // с потребностью u_ij принимает груз только от A{i} по c_ij, строка k с
+    // запасом u_ij отдает его в B{j} или обратно в столбец k бесплатно,
+    // поэтому через маршрут проходит не больше u_ij. Задача растет на
+    // столбец и строку на каждое ограничение, которое может сработать.
+    // Infeasible, если ограничения не позволяют закрыть потребности
+    pub fn solve_capacitated(&self) -> Result<TransportPlan, TransportError> {
+        let problem = self.balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+
+        let mut limited = Vec::new();
+        if let Some(capacities) = &problem.route_capacities {
+            for i in 0..m {
+                for j in 0..n {
+                    let capacity = capacities[i][j];
+                    if capacity > 0
+                        && capacity < problem.supplies[i].min(problem.demands[j])
+                        && !problem.is_forbidden(i, j)
+                    {
+                        limited.push((i, j, capacity));
+                    }
+                }
+            }
+        }
+
+        let mut expanded = problem.clone();
+        expanded.fixed_costs = None;
+        expanded.route_capacities = None;
+        for &(_, _, capacity) in &limited {
+            expanded
+                .costs
+                .push_col(vec![FORBIDDEN; expanded.supplies.len()]);
+            expanded.demands.push(capacity);
+        }
+        for (k, &(i, j, capacity)) in limited.iter().enumerate() {
+            expanded.costs[i][n + k] = problem.costs[i][j];
+            expanded.costs[i][j] = FORBIDDEN;
+            let mut row = vec![FORBIDDEN; n + limited.len()];
+            row[j] = 0;
+            row[n + k] = 0;
+            expanded.costs.push_row(row);
+            expanded.supplies.push(capacity);
+        }
+
+        // Метод минимального элемента обходит запрещенные клетки, пока есть
+        // выбор, и не раздувает стоимость начального плана
+        let start = expanded.least_cost();
+        let solved = expanded.optimize(start, &mut io::sink(), PivotRule::MostNegative);
+        if solved
+            .occupied_cells()
+            .any(|(i, j, _)| expanded.is_forbidden(i, j))
+        {
+            return Err(TransportError::Infeasible);
+        }
+
+        let mut allocations = Matrix::zeros(m, n);
+        for i in 0..m {
+            for j in 0..n {
+                allocations[i][j] = solved.allocations[i][j];
+            }
+        }
+        for (k, &(i, j, _)) in limited.iter().enumerate() {
+            allocations[i][j] = solved.allocations[m + k][j];
+        }
+
+        let total_cost = problem.calculate_total_cost(&allocations);
+        Ok(TransportPlan {
+            allocations,
+            total_cost,
+        })
+    }
+
+    // Многопериодная задача: self — первый период, periods — следующие; запас
+    // можно вывезти позже с доплатой holding_cost за единицу и период хранения
+    pub fn solve_multiperiod(
+        &self,
+        periods: &[TransportProblem],
+        holding_cost: i32,
+    ) -> Result<Vec<TransportPlan>, TransportError> {
+        let all: Vec<&TransportProblem> = std::iter::once(self).chain(periods).collect();
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        for period in &all {
+            if period.supplies.len() != m {
+                return Err(TransportError::DimensionMismatch {
+                    expected: m,
+                    found: period.supplies.len(),
+                });
+            }
+            if period.demands.len() != n {
+                return Err(TransportError::DimensionMismatch {
+                    expected: n,
+                    found: period.demands.len(),
+                });
+            }
+        }
+
+        let count = all.len();
+        let supplies: Vec<i32> = all.iter().flat_map(|p| p.supplies.clone()).collect();
+        let demands: Vec<i32> = all.iter().flat_map(|p| p.demands.clone()).collect();
+        let mut costs = vec![vec![FORBIDDEN; n * count]; m * count];
+        for t in 0..count {
+            for (later, period) in all.iter().enumerate().skip(t) {
+                for i in 0..m {
+                    for j in 0..n {
+                        if !period.is_forbidden(i, j) {
+                            costs[t * m + i][later * n + j] =
+                                period.costs[i][j] + holding_cost * (later - t) as i32;
+                        }
+                    }
+                }
+            }
+        }
+
+        let expanded = TransportProblem::try_new(supplies, demands, costs)?.balanced();
+        let start = expanded.least_cost();
+        let solved = expanded.optimize(start, &mut io::sink(), PivotRule::MostNegative);
+        if solved
+            .occupied_cells()
+            .any(|(i, j, _)| expanded.is_forbidden(i, j))
+        {
+            return Err(TransportError::Infeasible);
+        }
+
+        let mut plans = Vec::with_capacity(count);
+        for later in 0..count {
+            let mut allocations = Matrix::zeros(m, n);
+            let mut total_cost = 0;
+            for t in 0..=later {
+                for i in 0..m {
+                    for j in 0..n {
+                        let (row, col) = (t * m + i, later * n + j);
+                        let amount = solved.allocations[row][col];
+                        allocations[i][j] += amount;
+                        total_cost += amount * expanded.costs[row][col];
+                    }
+                }
+            }
+            plans.push(TransportPlan {
+                allocations,
+                total_cost,
+            });
+        }
+
+        Ok(plans)
+    }
+
+    // Строгое решение для проверочных конвейеров: вырожденный начальный
+    // план (северо-западный угол сбалансированной задачи) — ошибка, а не
+    // повод достроить базис эпсилон-клетками
+    pub fn solve_strict(&self) -> Result<TransportPlan, TransportError> {
+        let problem = self.balanced();
+        let start = problem.north_west_corner();
+        let missing = problem.degeneracy(&start);
+        if missing > 0 {
+            return Err(TransportError::Degenerate { missing });
+        }
+        Ok(problem.optimize(start, &mut io::sink(), PivotRule::MostNegative))
+    }
+
+    // Решение с отправкой событий в канал по ходу работы (для интерфейса,
+    // обновляемого в реальном времени): начальный план, каждый поворот и
+    // оптимальный план. Если получатель закрыт, решение прекращается; если
+    // оптимизация не сошлась за MAX_ITERATIONS, Optimal не отправляется.
+    // Канал закрывается вместе с tx по завершении
+    pub fn solve_streaming(&self, tx: Sender<SolveEvent>) {
+        let problem = self.balanced();
+        let start = problem.north_west_corner();
+        if tx.send(SolveEvent::InitialPlan(start.clone())).is_err() {
+            return;
+        }
+
+        let mut receiver_alive = true;
+        let result = problem.optimize_observed(
+            start,
+            &mut io::sink(),
+            PivotRule::MostNegative,
+            &mut |_, step| {
+                receiver_alive = tx.send(SolveEvent::Pivot(step.clone())).is_ok();
+                receiver_alive
+            },
+        );
+        if receiver_alive && result.converged {
+            let _ = tx.send(SolveEvent::Optimal(result.plan));
+        }
+    }
+
+    // Решение без вывода таблиц: начальный план и оптимизация
+    pub fn solve_to_plan(&self) -> TransportPlan {
+        let problem = self.balanced();
+        let plan = problem.north_west_corner();
+        problem.optimize(plan, &mut io::sink(), PivotRule::MostNegative)
+    }
+
+    // Каноническое решение — то же, что solve_to_plan: старт от северо-западного
+    // угла, при равных оценках вводится первая клетка в построчном порядке
+    // (строгое сравнение в поиске optimize_observed), при равных объемах
+    // выводится клетка с наименьшим (i, j) (shift_along_cycle)
+    pub fn solve_canonical(&self) -> TransportPlan {
+        self.solve_to_plan()
+    }
+
+    // Решение без проверки и приведения баланса — для горячих циклов, где
+    // баланс гарантирован построением. Ответственность на вызывающем:
+    // для несбалансированной задачи план будет неверным (часть запасов
+    // или потребностей останется нераспределенной), ошибка не выдается.
+    pub fn solve_assume_balanced(&self) -> TransportPlan {
+        let plan = self.north_west_corner();
+        self.optimize(plan, &mut io::sink(), PivotRule::MostNegative)
+    }
+
+    // Среди оптимальных планов — план с наименьшим числом маршрутов.
+    // Альтернативные оптимумы перебираются поворотами через небазисные
+    // клетки с нулевой оценкой; число просмотренных базисов ограничено
+    // MAX_ITERATIONS
+    pub fn solve_minimize_routes(&self) -> TransportPlan {
+        self.best_alternative_optimum(|problem, plan| problem.basis_size(plan))
+    }
+
+    // Лексикографическое решение с полностью определенным результатом.
+    // Приоритеты: 1) наименьшая стоимость; 2) среди оптимумов — наименьшее
+    // число маршрутов (как solve_minimize_routes); 3) при равенстве —
+    // наименьшая матрица перевозок в построчном лексикографическом порядке,
+    // так что результат не зависит от порядка перебора оптимумов
+    pub fn solve_lexicographic(&self) -> TransportPlan {
+        self.best_alternative_optimum(|problem, plan| {
+            (problem.basis_size(plan), plan.allocations.to_vec())
+        })
+    }
+
+    // Перебор альтернативных оптимумов с выбором плана с наименьшим ключом
+    fn best_alternative_optimum<K: Ord>(
+        &self,
+        key: impl Fn(&TransportProblem, &TransportPlan) -> K,
+    ) -> TransportPlan {
+        let problem = self.balanced();
+        let optimum = problem.solve_assume_balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+
+        let start = problem.basis_of(&optimum.allocations);
+        let mut seen = vec![start.clone()];
+        let mut queue = VecDeque::from([(optimum.allocations.clone(), start)]);
+        let mut best = optimum;
+
+        'search: while let Some((allocations, basis)) = queue.pop_front() {
+            let Ok((u, v)) = problem.potentials_for_basis(&basis) else {
+                continue;
+            };
+
+            for i in 0..m {
+                for j in 0..n {
+                    let delta = problem.reduced_cost(i, j, &u, &v);
+                    if basis[i][j] || delta.abs() >= EPSILON {
+                        continue;
+                    }
+
+                    let mut next = allocations.clone();
+                    let mut next_basis = basis.clone();
+                    if problem
+                        .pivot_in_basis(&mut next, &mut next_basis, i, j)
+                        .is_none()
+                        || seen.contains(&next_basis)
+                    {
+                        continue;
+                    }
+                    if seen.len() >= MAX_ITERATIONS {
+                        break 'search;
+                    }
+                    seen.push(next_basis.clone());
+
+                    let plan = TransportPlan {
+                        total_cost: problem.calculate_total_cost(&next),
+                        allocations: next.clone(),
+                    };
+                    if plan.total_cost == best.total_cost
+                        && key(&problem, &plan) < key(&problem, &best)
+                    {
+                        best = plan;
+                    }
+                    queue.push_back((next, next_basis));
+                }
+            }
+        }
+
+        best
+    }
+
+    // Базисный план наибольшей стоимости: минимизация дополненных стоимостей
+    // max - c на сбалансированной задаче. Запрещенные маршруты остаются
+    // запрещенными, доплаты учитываются только в итоговой стоимости
+    pub fn worst_plan(&self) -> TransportPlan {
+        let problem = self.balanced();
+        let max_cost = problem
+            .costs
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|&cost| cost < FORBIDDEN)
+            .max()
+            .unwrap_or(0);
+
+        let mut inverted = problem.clone();
+        inverted.fixed_costs = None;
+        for i in 0..inverted.costs.rows() {
+            for j in 0..inverted.costs.cols() {
+                if inverted.costs[i][j] < FORBIDDEN {
+                    inverted.costs[i][j] = max_cost - inverted.costs[i][j];
+                }
+            }
+        }
+
+        let allocations = inverted.solve_assume_balanced().allocations;
+        TransportPlan {
+            total_cost: problem.calculate_total_cost(&allocations),
+            allocations,
+        }
+    }
+
+    // Эвристика для задачи с фиксированными доплатами. Метод потенциалов
+    // линеен и доплат не видит, поэтому план строится жадно с минимумом
+    // маршрутов: каждый раз выбирается клетка с наименьшей стоимостью
+    // единицы с учетом доплаты, и по ней отгружается максимум. Возвращается
+    // лучший из жадного плана и линейного оптимума по полной стоимости
+    pub fn solve_fixed_charge(&self) -> TransportPlan {
+        let problem = self.balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+        let mut allocations = Matrix::zeros(m, n);
+        let mut supply_remaining = problem.supplies.clone();
+        let mut demand_remaining = problem.demands.clone();
+
+        loop {
+            let mut best: Option<(f64, usize, usize, i32)> = None;
+            for i in 0..m {
+                for j in 0..n {
+                    let amount = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+                    if amount <= 0 {
+                        continue;
+                    }
+                    let fixed = problem.fixed_costs.as_ref().map_or(0, |f| f[i][j]);
+                    let unit_cost = problem.costs[i][j] as f64 + fixed as f64 / amount as f64;
+                    if best.is_none_or(|(cost, ..)| unit_cost < cost) {
+                        best = Some((unit_cost, i, j, amount));
+                    }
+                }
+            }
+
+            let Some((_, i, j, amount)) = best else {
+                break;
+            };
+            allocations[i][j] += amount;
+            supply_remaining[i] -= amount;
+            demand_remaining[j] -= amount;
+        }
+
+        let total_cost = problem.calculate_total_cost(&allocations);
+        let greedy = TransportPlan {
+            allocations,
+            total_cost,
+        };
+        let linear = problem.solve_assume_balanced();
+
+        if linear.total_cost < greedy.total_cost {
+            linear
+        } else {
+            greedy
+        }
+    }
+
+    // Недорогой план не более чем с max_routes маршрутами. Точная задача
+    // NP-трудна, поэтому это эвристика: кандидаты — оптимум с наименьшим
+    // числом маршрутов и жадные планы задачи с фиксированными доплатами при
+    // нескольких размерах доплаты, из подходящих выбирается самый дешевый.
+    // Маршруты к фиктивным участникам не считаются. Infeasible, если ни один
+    // кандидат не уложился в ограничение (точный план при этом может существовать)
+    pub fn solve_with_max_routes(
+        &self,
+        max_routes: usize,
+    ) -> Result<TransportPlan, TransportError> {
+        let problem = self.balanced();
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let routes = |plan: &TransportPlan| {
+            plan.occupied_cells()
+                .filter(|&(i, j, _)| i < m && j < n)
+                .count()
+        };
+
+        // Доплата масштабируется по самой дорогой возможной перевозке
+        let max_cost = problem
+            .costs
+            .iter()
+            .flatten()
+            .copied()
+            .filter(|&cost| cost < FORBIDDEN)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let max_amount = problem.supplies.iter().copied().max().unwrap_or(0).max(1);
+        let scale = max_cost.saturating_mul(max_amount);
+
+        let mut candidates = vec![problem.solve_minimize_routes()];
+        for divisor in [1000, 100, 10, 1] {
+            let charge = (scale / divisor).max(1);
+            let mut charged = problem.clone();
+            let mut fixed = problem
+                .fixed_costs
+                .clone()
+                .unwrap_or_else(|| Matrix::zeros(problem.supplies.len(), problem.demands.len()));
+            for i in 0..fixed.rows() {
+                for j in 0..fixed.cols() {
+                    fixed[i][j] += charge;
+                }
+            }
+            charged.fixed_costs = Some(fixed);
+            let allocations = charged.solve_fixed_charge().allocations;
+            candidates.push(TransportPlan {
+                total_cost: problem.calculate_total_cost(&allocations),
+                allocations,
+            });
+        }
+
+        candidates
+            .into_iter()
+            .filter(|plan| routes(plan) <= max_routes)
+            .min_by_key(|plan| plan.total_cost)
+            .ok_or(TransportError::Infeasible)
+    }
+
+    // Решение для нескольких наборов потребностей при тех же запасах и стоимостях
+    pub fn solve_scenarios(
+        &self,
+        demand_sets: &[Vec<i32>],
+    ) -> Vec<Result<TransportPlan, TransportError>> {
+        demand_sets
+            .iter()
+            .map(|demands| self.solve_scenario(demands))
+            .collect()
+    }
+
+    // То же, что solve_scenarios, но сценарии решаются в нескольких потоках;
+    // порядок результатов совпадает с порядком наборов потребностей
+    pub fn solve_scenarios_parallel(
+        &self,
+        demand_sets: &[Vec<i32>],
+    ) -> Vec<Result<TransportPlan, TransportError>> {
+        parallel_map(demand_sets, |demands| self.solve_scenario(demands))
+    }
+
+    fn solve_scenario(&self, demands: &[i32]) -> Result<TransportPlan, TransportError> {
+        if demands.len() != self.demands.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.demands.len(),
+                found: demands.len(),
+            });
+        }
+        let scenario = TransportProblem {
+            demands: demands.to_vec(),
+            ..self.clone()
+        };
+        Ok(scenario.solve_to_plan())
+    }
+
+    // Начальный план выбранным методом
+    pub fn initial_plan(&self, method: InitialBasis) -> TransportPlan {
+        match method {
+            InitialBasis::NorthWestCorner => self.north_west_corner(),
+            InitialBasis::LeastCost => self.least_cost(),
+            InitialBasis::Vogel => self.vogel_approximation(),
+            InitialBasis::Russell => self.russell_approximation(),
+        }
+    }
+
+    // Только начальный план сбалансированной задачи, без метода потенциалов:
+    // показывает качество эвристики самой по себе
+    pub fn solve_no_optimize(&self, method: InitialBasis) -> TransportPlan {
+        self.balanced().initial_plan(method)
+    }
+
+    // Число поворотов метода потенциалов от начального плана выбранного
+    // метода до оптимума — мера качества эвристики помимо стоимости
+    pub fn pivots_to_optimum(&self, initial: InitialBasis) -> usize {
+        let problem = self.balanced();
+        let plan = problem.initial_plan(initial);
+        problem.optimize_with_callback(plan, |_| true).iterations
+    }
+
+    // Наименьшая, наибольшая стоимость и размах начальных планов
+    // северо-западного угла, минимального элемента, Фогеля и Рассела.
+    // Большой размах — выбор начального метода для задачи существенен
+    pub fn initial_cost_spread(&self) -> (i32, i32, i32) {
+        let costs: Vec<i32> = [
+            InitialBasis::NorthWestCorner,
+            InitialBasis::LeastCost,
+            InitialBasis::Vogel,
+            InitialBasis::Russell,
+        ]
+        .into_iter()
+        .map(|method| self.solve_no_optimize(method).total_cost)
+        .collect();
+        let low = *costs.iter().min().unwrap();
+        let high = *costs.iter().max().unwrap();
+        (low, high, high - low)
+    }
+
+    // Метод северо-западного угла
+    pub fn north_west_corner(&self) -> TransportPlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut allocations = Matrix::zeros(m, n);
+
+        let mut supply_remaining = self.supplies.clone();
+        let mut demand_remaining = self.demands.clone();
+
+        let mut i = 0;
+        let mut j = 0;
+
+        while i < m && j < n {
+            let allocation = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+            allocations[i][j] = allocation;
+            supply_remaining[i] -= allocation;
+            demand_remaining[j] -= allocation;
+
+            if supply_remaining[i] == 0 {
+                i += 1;
+            }
+            if demand_remaining[j] == 0 {
+                j += 1;
+            }
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+
+        TransportPlan {
+            allocations,
+            total_cost,
+        }
+    }
+
+    // Северо-западный угол с заданным порядком обхода: поставщики
+    // перебираются в порядке supply_order, потребители — в порядке
+    // demand_order (например, сначала более дешевые в эксплуатации склады).
+    // Перевозки записываются по исходным индексам. Порядки должны быть
+    // перестановками индексов, иначе паника
+    pub fn north_west_corner_ordered(
+        &self,
+        supply_order: &[usize],
+        demand_order: &[usize],
+    ) -> TransportPlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let is_permutation = |order: &[usize], len: usize| {
+            let mut seen = vec![false; len];
+            order.len() == len
+                && order
+                    .iter()
+                    .all(|&k| k < len && !std::mem::replace(&mut seen[k], true))
+        };
+        assert!(
+            is_permutation(supply_order, m),
+            "порядок поставщиков не является перестановкой"
+        );
+        assert!(
+            is_permutation(demand_order, n),
+            "порядок потребителей не является перестановкой"
+        );
+
+        let mut allocations = Matrix::zeros(m, n);
+        let mut supply_remaining = self.supplies.clone();
+        let mut demand_remaining = self.demands.clone();
+
+        let mut a = 0;
+        let mut b = 0;
+        while a < m && b < n {
+            let (i, j) = (supply_order[a], demand_order[b]);
+            let allocation = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+            allocations[i][j] = allocation;
+            supply_remaining[i] -= allocation;
+            demand_remaining[j] -= allocation;
+
+            if supply_remaining[i] == 0 {
+                a += 1;
+            }
+            if demand_remaining[j] == 0 {
+                b += 1;
+            }
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+
+        TransportPlan {
+            allocations,
+            total_cost,
+        }
+    }
+
+    // Метод минимального элемента: груз отгружается по самой дешевой
+    // из оставшихся клеток, при равенстве — по первой в построчном порядке
+    pub fn least_cost(&self) -> TransportPlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut allocations = Matrix::zeros(m, n);
+
+        let mut supply_remaining = self.supplies.clone();
+        let mut demand_remaining = self.demands.clone();
+        let mut row_done = vec![false; m];
+        let mut col_done = vec![false; n];
+
+        loop {
+            let mut cheapest: Option<(usize, usize)> = None;
+            for i in (0..m).filter(|&i| !row_done[i]) {
+                for j in (0..n).filter(|&j| !col_done[j]) {
+                    if cheapest.is_none_or(|(bi, bj)| self.costs[i][j] < self.costs[bi][bj]) {
+                        cheapest = Some((i, j));
+                    }
+                }
+            }
+            let Some((i, j)) = cheapest else {
+                break;
+            };
+
+            let allocation = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+            allocations[i][j] = allocation;
+            supply_remaining[i] -= allocation;
+            demand_remaining[j] -= allocation;
+            row_done[i] = supply_remaining[i] == 0;
+            col_done[j] = demand_remaining[j] == 0;
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+
+        TransportPlan {
+            allocations,
+            total_cost,
+        }
+    }
+
+    // Метод Фогеля: штраф строки или столбца — разность двух наименьших
+    // стоимостей среди оставшихся клеток. Груз отгружается по самой дешевой
+    // клетке линии с наибольшим штрафом (строки раньше столбцов)
+    pub fn vogel_approximation(&self) -> TransportPlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut allocations = Matrix::zeros(m, n);
+
+        let mut supply_remaining = self.supplies.clone();
+        let mut demand_remaining = self.demands.clone();
+        let mut row_done = vec![false; m];
+        let mut col_done = vec![false; n];
+
+        // Штраф по набору стоимостей; единственная стоимость — сама себе штраф
+        let penalty = |mut costs: Vec<i32>| {
+            costs.sort_unstable();
+            match costs.as_slice() {
+                [] => None,
+                [only] => Some(*only),
+                [first, second, ..] => Some(second - first),
+            }
+        };
+
+        loop {
+            let open_rows: Vec<usize> = (0..m).filter(|&i| !row_done[i]).collect();
+            let open_cols: Vec<usize> = (0..n).filter(|&j| !col_done[j]).collect();
+            if open_rows.is_empty() || open_cols.is_empty() {
+                break;
+            }
+
+            // (штраф, строка?, индекс линии)
+            let mut line: Option<(i32, bool, usize)> = None;
+            for &i in &open_rows {
+                if let Some(p) = penalty(open_cols.iter().map(|&j| self.costs[i][j]).collect())
+                    && line.is_none_or(|(best, _, _)| p > best)
+                {
+                    line = Some((p, true, i));
+                }
+            }
+            for &j in &open_cols {
+                if let Some(p) = penalty(open_rows.iter().map(|&i| self.costs[i][j]).collect())
+                    && line.is_none_or(|(best, _, _)| p > best)
+                {
+                    line = Some((p, false, j));
+                }
+            }
+            let Some((_, is_row, k)) = line else {
+                break;
+            };
+
+            let (i, j) = if is_row {
+                let j = *open_cols.iter().min_by_key(|&&j| self.costs[k][j]).unwrap();
+                (k, j)
+            } else {
+                let i = *open_rows.iter().min_by_key(|&&i| self.costs[i][k]).unwrap();
+                (i, k)
+            };
+
+            let allocation = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+            allocations[i][j] = allocation;
+            supply_remaining[i] -= allocation;
+            demand_remaining[j] -= allocation;
+            row_done[i] = supply_remaining[i] == 0;
+            col_done[j] = demand_remaining[j] == 0;
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+
+        TransportPlan {
+            allocations,
+            total_cost,
+        }
+    }
+
+    // Аппроксимация Рассела: для каждой открытой строки u_i — наибольшая
+    // разрешенная стоимость в ней, для столбца v_j — в столбце; груз
+    // отгружается по клетке с наименьшей оценкой c_ij - u_i - v_j, при
+    // равенстве — по первой в построчном порядке
+    pub fn russell_approximation(&self) -> TransportPlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut allocations = Matrix::zeros(m, n);
+
+        let mut supply_remaining = self.supplies.clone();
+        let mut demand_remaining = self.demands.clone();
+        let mut row_done = vec![false; m];
+        let mut col_done = vec![false; n];
+
+        loop {
+            let open_rows: Vec<usize> = (0..m).filter(|&i| !row_done[i]).collect();
+            let open_cols: Vec<usize> = (0..n).filter(|&j| !col_done[j]).collect();
+            if open_rows.is_empty() || open_cols.is_empty() {
+                break;
+            }
+
+            // Запрещенные клетки в максимумах не участвуют; линия из одних
+            // запрещенных клеток получает FORBIDDEN
+            let u: Vec<i64> = (0..m)
+                .map(|i| {
+                    self.cost_range(open_cols.iter().map(|&j| (i, j)))
+                        .map_or(FORBIDDEN, |(_, hi)| hi) as i64
+                })
+                .collect();
+            let v: Vec<i64> = (0..n)
+                .map(|j| {
+                    self.cost_range(open_rows.iter().map(|&i| (i, j)))
+                        .map_or(FORBIDDEN, |(_, hi)| hi) as i64
+                })
+                .collect();
+
+            let mut best: Option<(usize, usize, i64)> = None;
+            for &i in &open_rows {
+                for &j in &open_cols {
+                    let delta = self.costs[i][j] as i64 - u[i] - v[j];
+                    if best.is_none_or(|(_, _, b)| delta < b) {
+                        best = Some((i, j, delta));
+                    }
+                }
+            }
+            let Some((i, j, _)) = best else {
+                break;
+            };
+
+            let allocation = std::cmp::min(supply_remaining[i], demand_remaining[j]);
+            allocations[i][j] = allocation;
+            supply_remaining[i] -= allocation;
+            demand_remaining[j] -= allocation;
+            row_done[i] = supply_remaining[i] == 0;
+            col_done[j] = demand_remaining[j] == 0;
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+
+        TransportPlan {
+            allocations,
+            total_cost,
+        }
+    }
+
+    // План по заданному базису: объемы в m + n - 1 клетках однозначно
+    // находятся из запасов и потребностей, если клетки образуют дерево.
+    // Индексы относятся к сбалансированной задаче (с фиктивной строкой или
+    // столбцом, как в solve_to_plan). Базис, дающий отрицательные объемы,
+    // допустимого плана не задает
+    pub fn plan_from_basis(
+        &self,
+        basis: &[(usize, usize)],
+    ) -> Result<TransportPlan, TransportError> {
+        let problem = self.balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+
+        if basis.len() != m + n - 1 {
+            return Err(TransportError::DimensionMismatch {
+                expected: m + n - 1,
+                found: basis.len(),
+            });
+        }
+        let mut cells = vec![vec![false; n]; m];
+        for &(i, j) in basis {
+            if i >= m {
+                return Err(TransportError::IndexOutOfRange { index: i, len: m });
+            }
+            if j >= n {
+                return Err(TransportError::IndexOutOfRange { index: j, len: n });
+            }
+            cells[i][j] = true;
+        }
+        // m + n - 1 клеток, связывающих все строки и столбцы, — это дерево;
+        // повторы и циклы оставляют часть строк или столбцов несвязанной
+        if problem.potentials_for_basis(&cells).is_err() {
+            return Err(TransportError::BasisNotTree);
+        }
+
+        // Обратный ход: строка или столбец с единственной клеткой базиса
+        // целиком определяет ее объем, после чего клетка исключается
+        let mut supply_remaining = problem.supplies.clone();
+        let mut demand_remaining = problem.demands.clone();
+        let mut row_count: Vec<usize> = cells
+            .iter()
+            .map(|row| row.iter().filter(|&&c| c).count())
+            .collect();
+        let mut col_count: Vec<usize> = (0..n)
+            .map(|j| cells.iter().filter(|row| row[j]).count())
+            .collect();
+        let mut allocations = Matrix::zeros(m, n);
+
+        for _ in 0..basis.len() {
+            let (i, j, amount) = if let Some(i) = (0..m).find(|&i| row_count[i] == 1) {
+                let j = (0..n).find(|&j| cells[i][j]).unwrap();
+                (i, j, supply_remaining[i])
+            } else {
+                let j = (0..n).find(|&j| col_count[j] == 1).unwrap();
+                let i = (0..m).find(|&i| cells[i][j]).unwrap();
+                (i, j, demand_remaining[j])
+            };
+            allocations[i][j] = amount;
+            supply_remaining[i] -= amount;
+            demand_remaining[j] -= amount;
+            cells[i][j] = false;
+            row_count[i] -= 1;
+            col_count[j] -= 1;
+        }
+
+        let negative: Vec<(usize, usize)> = basis
+            .iter()
+            .copied()
+            .filter(|&(i, j)| allocations[i][j] < 0)
+            .collect();
+        if !negative.is_empty() {
+            return Err(TransportError::NegativeAllocation { cells: negative });
+        }
+
+        let total_cost = problem.calculate_total_cost(&allocations);
+        Ok(TransportPlan {
+            allocations,
+            total_cost,
+        })
+    }
+
+    pub fn calculate_total_cost(&self, allocations: &[Vec<i32>]) -> i32 {
+        let mut total = 0;
+        for i in 0..allocations.len() {
+            for j in 0..allocations[i].len() {
+                total += allocations[i][j] * self.costs[i][j];
+                if let Some(fixed) = &self.fixed_costs
+                    && allocations[i][j] != 0
+                {
+                    total += fixed[i][j];
+                }
+            }
+        }
+        total
+    }
+
+    // Стоимость произвольной матрицы перевозок с проверкой размеров по
+    // матрице стоимостей: несовпадение — ошибка, а не паника на индексе
+    pub fn evaluate(&self, allocations: &[Vec<i32>]) -> Result<i32, TransportError> {
+        self.check_dimensions(allocations)?;
+        Ok(self.calculate_total_cost(allocations))
+    }
+
+    fn check_dimensions(&self, allocations: &[Vec<i32>]) -> Result<(), TransportError> {
+        if allocations.len() != self.costs.rows() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.costs.rows(),
+                found: allocations.len(),
+            });
+        }
+        for (i, row) in allocations.iter().enumerate() {
+            if row.len() != self.costs.cols() {
+                return Err(TransportError::RaggedRow {
+                    row: i,
+                    expected: self.costs.cols(),
+                    found: row.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Стоимость плана с проверкой запрещенных маршрутов: перевозка по клетке
+    // со стоимостью FORBIDDEN — ошибка, а не огромная (или переполненная)
+    // сумма. calculate_total_cost этого не проверяет, так как начальные
+    // планы метода потенциалов могут временно занимать такие клетки
+    pub fn try_calculate_total_cost(
+        &self,
+        allocations: &[Vec<i32>],
+    ) -> Result<i32, TransportError> {
+        self.check_dimensions(allocations)?;
+        for (i, row) in allocations.iter().enumerate() {
+            for (j, &allocation) in row.iter().enumerate() {
+                if allocation != 0 && self.is_forbidden(i, j) {
+                    return Err(TransportError::ForbiddenRouteUsed { i, j });
+                }
+            }
+        }
+        Ok(self.calculate_total_cost(allocations))
+    }
+
+    // Оптимизация методом потенциалов
+    pub fn optimize_by_potentials(&self, plan: TransportPlan) -> TransportPlan {
+        self.optimize(plan, &mut io::stdout(), PivotRule::MostNegative)
+    }
+
+    // Оптимизация с уведомлением после каждого поворота (например, для
+    // индикатора прогресса); если callback возвращает false, оптимизация
+    // прерывается и возвращается текущий план
+    pub fn optimize_with_callback<F: FnMut(&OptimizationResult) -> bool>(
+        &self,
+        plan: TransportPlan,
+        mut callback: F,
+    ) -> OptimizationResult {
+        self.optimize_observed(
+            plan,
+            &mut io::sink(),
+            PivotRule::MostNegative,
+            &mut |result, _| callback(result),
+        )
+    }
+
+    // Оптимизация с записью каждого поворота: вводимая клетка, цикл,
+    // перемещенный объем и стоимость после поворота
+    pub fn optimize_with_history(
+        &self,
+        plan: TransportPlan,
+    ) -> (OptimizationResult, Vec<IterationStep>) {
+        let mut history = Vec::new();
+        let result = self.optimize_observed(
+            plan,
+            &mut io::sink(),
+            PivotRule::MostNegative,
+            &mut |_, step| {
+                history.push(step.clone());
+                true
+            },
+        );
+        (result, history)
+    }
+
+    // Оптимизация с заданным правилом выбора вводимой клетки; число итераций
+    // в результате позволяет сравнивать правила между собой
+    pub fn optimize_with_rule(&self, plan: TransportPlan, rule: PivotRule) -> OptimizationResult {
+        self.optimize_observed(plan, &mut io::sink(), rule, &mut |_, _| true)
+    }
+
+    fn optimize(&self, plan: TransportPlan, log: &mut dyn Write, rule: PivotRule) -> TransportPlan {
+        self.optimize_observed(plan, log, rule, &mut |_, _| true)
+            .plan
+    }
+
+    // Основной цикл метода потенциалов; on_pivot вызывается после каждого
+    // поворота и может прервать оптимизацию, вернув false
+    fn optimize_observed(
+        &self,
+        plan: TransportPlan,
+        log: &mut dyn Write,
+        rule: PivotRule,
+        on_pivot: &mut dyn FnMut(&OptimizationResult, &IterationStep) -> bool,
+    ) -> OptimizationResult {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut iteration = 0;
+        let mut basis = self.basis_of(&plan.allocations);
+        let mut result = OptimizationResult {
+            plan,
+            iterations: 0,
+            converged: false,
+            error: None,
+        };
+        // Дальше стоимость обновляется по дельте поворота, поэтому начальная
+        // должна быть точной, даже если план собран вручную
+        result.plan.recompute_cost(self);
+
+        while iteration < MAX_ITERATIONS {
+            iteration += 1;
+
+            // Шаг 1: Вычисление потенциалов по текущему базису
+            let (u, v) = match self.repaired_potentials(&mut basis, &result.plan.allocations) {
+                Ok(potentials) => potentials,
+                Err(error) => {
+                    let _ = writeln!(log, "Итерация {}: {}", iteration, error);
+                    result.error = Some(error.into());
+                    break;
+                }
+            };
+
+            // Шаг 2: Поиск улучшающей клетки по выбранному правилу
+            let mut best: Option<(usize, usize, f64)> = None;
+            let mut best_score = 0.0;
+
+            'search: for i in 0..m {
+                for j in 0..n {
+                    if basis[i][j] {
+                        continue;
+                    }
+                    let delta = self.reduced_cost(i, j, &u, &v);
+                    if delta > -EPSILON {
+                        continue;
+                    }
+                    let score = match rule {
+                        // Оцениваем фактическое снижение стоимости по циклу
+                        PivotRule::LargestImprovement => {
+                            let mut trial = result.plan.allocations.clone();
+                            let mut trial_basis = basis.clone();
+                            match self.pivot_in_basis(&mut trial, &mut trial_basis, i, j) {
+                                Some(min_q) => cost_change(delta, min_q) as f64,
+                                None => continue,
+                            }
+                        }
+                        PivotRule::MostNegative | PivotRule::FirstNegative => delta,
+                    };
+                    let better = match best {
+                        None => true,
+                        Some((_, _, best_delta)) => {
+                            score < best_score || (score == best_score && delta < best_delta)
+                        }
+                    };
+                    if better {
+                        best_score = score;
+                        best = Some((i, j, delta));
+                        if rule == PivotRule::FirstNegative {
+                            break 'search;
+                        }
+                    }
+                }
+            }
+
+            // Шаг 3: Если найдена улучшающая клетка
+            let Some((best_i, best_j, best_delta)) = best else {
+                result.converged = true;
+                // Журнал итераций вспомогательный: ошибки записи не прерывают решение
+                let _ = writeln!(log, "Итерация {}: план оптимален", iteration);
+                break;
+            };
+
+            if iteration % self.print_every.max(1) == 0 {
+                let _ = writeln!(
+                    log,
+                    "Итерация {}: улучшение через клетку ({}, {}) с дельтой {:.2}",
+                    iteration,
+                    best_i + 1,
+                    best_j + 1,
+                    best_delta
+                );
+            }
+
+            let previous = result.plan.allocations.clone();
+            let Some(cycle) = self.find_cycle(&basis, best_i, best_j) else {
+                break;
+            };
+            let Some(min_q) =
+                Self::shift_along_cycle(&mut result.plan.allocations, &mut basis, &cycle)
+            else {
+                break;
+            };
+            // Неверный цикл дал отрицательный груз: откатываем, сообщаем и прекращаем
+            if let Err(error) = result.plan.assert_nonnegative() {
+                let _ = writeln!(log, "Итерация {}: ошибка поворота: {}", iteration, error);
+                result.plan.allocations = previous;
+                result.error = Some(error);
+                break;
+            }
+            // Поворот меняет только клетки цикла: стоимость падает на
+            // оценку × объем. Доплаты зависят от набора занятых клеток,
+            // поэтому с ними стоимость пересчитывается полностью
+            if self.fixed_costs.is_some() {
+                result.plan.recompute_cost(self);
+            } else {
+                result.plan.total_cost += cost_change(best_delta, min_q);
+                debug_assert!(
+                    result.plan.cost_is_consistent(self),
+                    "стоимость после поворота разошлась с полным пересчетом"
+                );
+            }
+            result.iterations += 1;
+            let step = IterationStep {
+                iteration: result.iterations,
+                entering: (best_i, best_j),
+                delta: best_delta,
+                amount: min_q,
+                cycle,
+                cost: result.plan.total_cost,
+            };
+            if !on_pivot(&result, &step) {
+                break;
+            }
+        }
+
+        result
+    }
+
+    // Потенциалы по базису; если он несвязный, базис заново строится по
+    // занятым клеткам плана, а ошибка возвращается, только если не помогло и это
+    fn repaired_potentials(
+        &self,
+        basis: &mut Vec<Vec<bool>>,
+        allocations: &[Vec<i32>],
+    ) -> Result<(Vec<f64>, Vec<f64>), PotentialError> {
+        self.potentials_for_basis(basis).or_else(|_| {
+            *basis = self.basis_of(allocations);
+            self.potentials_for_basis(basis)
+        })
+    }
+
+    // Базис плана: занятые клетки, а если их не хватает для связности
+    // (вырожденный план) — эпсилон-клетки, то есть нулевые перевозки по
+    // самым дешевым маршрутам, соединяющим части базиса
+    fn basis_of(&self, allocations: &[Vec<i32>]) -> Vec<Vec<bool>> {
+        let mut basis: Vec<Vec<bool>> = allocations
+            .iter()
+            .map(|row| row.iter().map(|&x| x > 0).collect())
+            .collect();
+
+        loop {
+            let (missing_rows, missing_cols) = match self.potentials_for_basis(&basis) {
+                Ok(_) => return basis,
+                Err(PotentialError::Disconnected {
+                    missing_rows,
+                    missing_cols,
+                }) => (missing_rows, missing_cols),
+            };
+
+            let mut epsilon: Option<(usize, usize)> = None;
+            for i in 0..self.supplies.len() {
+                for j in 0..self.demands.len() {
+                    let connects = missing_rows.contains(&i) != missing_cols.contains(&j);
+                    if connects
+                        && !basis[i][j]
+                        && epsilon.is_none_or(|(bi, bj)| self.costs[i][j] < self.costs[bi][bj])
+                    {
+                        epsilon = Some((i, j));
+                    }
+                }
+            }
+
+            match epsilon {
+                Some((i, j)) => basis[i][j] = true,
+                None => return basis,
+            }
+        }
+    }
+
+    // Базис, в котором оценки всех клеток неотрицательны. У вырожденного
+    // оптимума эпсилон-клетки из basis_of могут дать отрицательные оценки;
+    // тогда выполняются повороты с нулевым объемом, план не меняется.
+    // Для неоптимального плана повороты останавливаются на первом,
+    // который сдвинул бы груз
+    fn optimal_basis(&self, allocations: &Matrix) -> Vec<Vec<bool>> {
+        let mut basis = self.basis_of(allocations);
+
+        for _ in 0..MAX_ITERATIONS {
+            let Ok((u, v)) = self.potentials_for_basis(&basis) else {
+                break;
+            };
+            let mut entering: Option<(usize, usize, f64)> = None;
+            for i in 0..self.supplies.len() {
+                for j in 0..self.demands.len() {
+                    let delta = self.reduced_cost(i, j, &u, &v);
+                    if !basis[i][j]
+                        && delta <= -EPSILON
+                        && entering.is_none_or(|(_, _, best)| delta < best)
+                    {
+                        entering = Some((i, j, delta));
+                    }
+                }
+            }
+            let Some((i, j, _)) = entering else {
+                break;
+            };
+
+            let mut next = allocations.clone();
+            let mut next_basis = basis.clone();
+            match self.pivot_in_basis(&mut next, &mut next_basis, i, j) {
+                Some(0) => basis = next_basis,
+                _ => break,
+            }
+        }
+
+        basis
+    }
+
+    // Потенциалы по занятым клеткам без достройки базиса: ошибка, если
+    // часть строк или столбцов не связана с базисом
+    pub fn try_compute_potentials(
+        &self,
+        allocations: &[Vec<i32>],
+    ) -> Result<(Vec<f64>, Vec<f64>), PotentialError> {
+        let basis: Vec<Vec<bool>> = allocations
+            .iter()
+            .map(|row| row.iter().map(|&x| x > 0).collect())
+            .collect();
+        self.potentials_for_basis(&basis)
+    }
+
+    // Оценка клетки c_ij - u_i - v_j. Потенциалы целочисленных стоимостей
+    // целые, но хранятся в f64; сравнение с нулем — с допуском EPSILON
+    fn reduced_cost(&self, i: usize, j: usize, u: &[f64], v: &[f64]) -> f64 {
+        self.costs[i][j] as f64 - u[i] - v[j]
+    }
+
+    fn potentials_for_basis(
+        &self,
+        basis: &[Vec<bool>],
+    ) -> Result<(Vec<f64>, Vec<f64>), PotentialError> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+
+        let mut u = vec![None; m];
+        let mut v = vec![None; n];
+        if m > 0 {
+            u[0] = Some(0.0);
+        }
+
+        // Распространение потенциалов через базисные клетки
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for i in 0..m {
+                for j in 0..n {
+                    if basis[i][j] {
+                        if let Some(u_val) = u[i] {
+                            if v[j].is_none() {
+                                v[j] = Some(self.costs[i][j] as f64 - u_val);
+                                changed = true;
+                            }
+                        } else if let Some(v_val) = v[j]
+                            && u[i].is_none()
+                        {
+                            u[i] = Some(self.costs[i][j] as f64 - v_val);
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let missing_rows: Vec<usize> = (0..m).filter(|&i| u[i].is_none()).collect();
+        let missing_cols: Vec<usize> = (0..n).filter(|&j| v[j].is_none()).collect();
+        if !missing_rows.is_empty() || !missing_cols.is_empty() {
+            return Err(PotentialError::Disconnected {
+                missing_rows,
+                missing_cols,
+            });
+        }
+
+        Ok((
+            u.into_iter().flatten().collect(),
+            v.into_iter().flatten().collect(),
+        ))
+    }
+
+    // Перераспределение груза по циклу через клетку (start_i, start_j);
+    // возвращает перемещенный объем или None, если цикл не найден
+    fn pivot(&self, allocations: &mut Matrix, start_i: usize, start_j: usize) -> Option<i32> {
+        let mut basis = self.basis_of(allocations);
+        self.pivot_in_basis(allocations, &mut basis, start_i, start_j)
+    }
+
+    // Поворот при известном базисе: клетка вводится в базис, из базиса
+    // выводится первая по циклу отнимающая клетка с минимальным грузом
+    fn pivot_in_basis(
+        &self,
+        allocations: &mut Matrix,
+        basis: &mut [Vec<bool>],
+        start_i: usize,
+        start_j: usize,
+    ) -> Option<i32> {
+        let cycle = self.find_cycle(basis, start_i, start_j)?;
+        Self::shift_along_cycle(allocations, basis, &cycle)
+    }
+
+    // Сдвиг груза по найденному циклу: первая клетка цикла вводится в базис
+    fn shift_along_cycle(
+        allocations: &mut Matrix,
+        basis: &mut [Vec<bool>],
+        cycle: &[(usize, usize)],
+    ) -> Option<i32> {
+        let (start_i, start_j) = *cycle.first()?;
+
+        // Находим минимальный груз в отнимающих клетках; при равенстве
+        // выводится клетка с наименьшим (i, j)
+        let &(leave_i, leave_j) = cycle
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .min_by_key(|&&(i, j)| (allocations[i][j], i, j))?;
+        let min_q = allocations[leave_i][leave_j];
+
+        // Перераспределение
+        for (idx, &(i, j)) in cycle.iter().enumerate() {
+            if idx % 2 == 0 {
+                // Четные - добавляем
+                allocations[i][j] += min_q;
+            } else {
+                // Нечетные - вычитаем
+                allocations[i][j] -= min_q;
+            }
+        }
+
+        basis[start_i][start_j] = true;
+        basis[leave_i][leave_j] = false;
+        Some(min_q)
+    }
+
+    // Поиск цикла пересчета: базис — дерево на строках и столбцах, поэтому
+    // путь по базисным клеткам из строки start_i в столбец start_j единственен.
+    // Цикл начинается с вводимой клетки, знаки клеток чередуются
+    fn find_cycle(
+        &self,
+        basis: &[Vec<bool>],
+        start_i: usize,
+        start_j: usize,
+    ) -> Option<Vec<(usize, usize)>> {
+        let m = basis.len();
+        let n = basis.first().map_or(0, |row| row.len());
+
+        // Вершины: строки 0..m и столбцы m..m+n, ребра — базисные клетки
+        let mut parent: Vec<Option<usize>> = vec![None; m + n];
+        let mut visited = vec![false; m + n];
+        let mut queue = VecDeque::new();
+
+        visited[start_i] = true;
+        queue.push_back(start_i);
+
+        while let Some(node) = queue.pop_front() {
+            if node == m + start_j {
+                break;
+            }
+            let neighbours: Vec<usize> = if node < m {
+                (0..n)
+                    .filter(|&j| basis[node][j] && (node, j) != (start_i, start_j))
+                    .map(|j| m + j)
+                    .collect()
+            } else {
+                let j = node - m;
+                (0..m)
+                    .filter(|&i| basis[i][j] && (i, j) != (start_i, start_j))
+                    .collect()
+            };
+            for next in neighbours {
+                if !visited[next] {
+                    visited[next] = true;
+                    parent[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !visited[m + start_j] {
+            return None;
+        }
+
+        // Строим путь назад от столбца start_j к строке start_i
+        let mut cycle = vec![(start_i, start_j)];
+        let mut node = m + start_j;
+        while let Some(prev) = parent[node] {
+            let cell = if node < m {
+                (node, prev - m)
+            } else {
+                (prev, node - m)
+            };
+            cycle.push(cell);
+            node = prev;
+        }
+
+        Some(cycle)
+    }
+
+    // Наименьшая и наибольшая стоимость среди разрешенных клеток; None, если
+    // все клетки запрещены. Общая основа диапазонов строк, столбцов и матрицы
+    fn cost_range(&self, cells: impl Iterator<Item = (usize, usize)>) -> Option<(i32, i32)> {
+        cells
+            .filter(|&(i, j)| !self.is_forbidden(i, j))
+            .map(|(i, j)| self.costs[i][j])
+            .fold(None, |range, c| match range {
+                None => Some((c, c)),
+                Some((lo, hi)) => Some((lo.min(c), hi.max(c))),
+            })
+    }
+
+    // Минимальная и максимальная стоимость в каждом столбце без запрещенных
+    // маршрутов; (0, 0) для столбца, где разрешенных маршрутов нет
+    pub fn column_cost_range(&self) -> Vec<(i32, i32)> {
+        let m = self.costs.rows();
+        (0..self.costs.cols())
+            .map(|j| self.cost_range((0..m).map(|i| (i, j))).unwrap_or((0, 0)))
+            .collect()
+    }
+
+    // Наименьшая и наибольшая стоимость матрицы без запрещенных маршрутов;
+    // (0, 0), если разрешенных маршрутов нет
+    pub fn cost_magnitude(&self) -> (i32, i32) {
+        let n = self.demands.len();
+        self.cost_range((0..self.supplies.len()).flat_map(|i| (0..n).map(move |j| (i, j))))
+            .unwrap_or((0, 0))
+    }
+
+    // Минимальная и максимальная стоимость в каждой строке без запрещенных
+    // маршрутов; (0, 0) для строки, где разрешенных маршрутов нет
+    pub fn row_cost_range(&self) -> Vec<(i32, i32)> {
+        let n = self.costs.cols();
+        (0..self.costs.rows())
+            .map(|i| self.cost_range((0..n).map(|j| (i, j))).unwrap_or((0, 0)))
+            .collect()
+    }
+
+    // Приведенная матрица стоимостей, как в первых шагах венгерского
+    // метода: из каждой строки вычитается ее минимум, затем из каждого
+    // столбца — минимум столбца. Нули отмечают дешевые маршруты-кандидаты.
+    // Запрещенные маршруты в минимумах не участвуют и остаются FORBIDDEN
+    pub fn row_column_reduced_costs(&self) -> Vec<Vec<i32>> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut reduced: Vec<Vec<i32>> = self.costs.to_vec();
+
+        for i in 0..m {
+            let allowed = (0..n).filter(|&j| !self.is_forbidden(i, j));
+            if let Some(min) = allowed.clone().map(|j| reduced[i][j]).min() {
+                for j in allowed {
+                    reduced[i][j] -= min;
+                }
+            }
+        }
+        for j in 0..n {
+            let allowed = (0..m).filter(|&i| !self.is_forbidden(i, j));
+            if let Some(min) = allowed.clone().map(|i| reduced[i][j]).min() {
+                for i in allowed {
+                    reduced[i][j] -= min;
+                }
+            }
+        }
+
+        reduced
+    }
+
+    // Проверка симметричности матрицы расстояний: пары (i, j), i < j, где
+    // costs[i][j] != costs[j][i]. Неквадратная матрица симметричной быть
+    // не может — для нее возвращается ошибка с пустым списком
+    pub fn assert_symmetric_costs(&self) -> Result<(), Vec<(usize, usize)>> {
+        let size = self.costs.rows();
+        if size != self.costs.cols() {
+            return Err(Vec::new());
+        }
+
+        let mut violations = Vec::new();
+        for i in 0..size {
+            for j in i + 1..size {
+                if self.costs[i][j] != self.costs[j][i] {
+                    violations.push((i, j));
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    // Проверка неравенства треугольника для квадратной матрицы расстояний:
+    // тройки (i, k, j) различных пунктов, где прямой путь дороже объезда,
+    // costs[i][j] > costs[i][k] + costs[k][j]. Тройки с запрещенными
+    // маршрутами пропускаются; для неквадратной матрицы список пуст
+    pub fn violates_triangle_inequality(&self) -> Vec<(usize, usize, usize)> {
+        let size = self.costs.rows();
+        if size != self.costs.cols() {
+            return Vec::new();
+        }
+
+        let mut violations = Vec::new();
+        for i in 0..size {
+            for j in (0..size).filter(|&j| j != i && !self.is_forbidden(i, j)) {
+                for k in (0..size).filter(|&k| k != i && k != j) {
+                    if !self.is_forbidden(i, k)
+                        && !self.is_forbidden(k, j)
+                        && self.costs[i][j] > self.costs[i][k] + self.costs[k][j]
+                    {
+                        violations.push((i, k, j));
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+
+    // Маршруты, не входящие ни в один оптимальный план: у другого поставщика k
+    // хватает запаса на весь спрос j, и обмен i→j, k→l на i→l, k→j дешевле при любом l
+    pub fn dominated_routes(&self) -> Vec<(usize, usize)> {
+        let problem = self.balanced();
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let columns = problem.demands.len();
+        let mut routes = Vec::new();
+
+        for i in 0..m {
+            for j in (0..n).filter(|&j| !self.is_forbidden(i, j)) {
+                let dominated = (0..m).any(|k| {
+                    k != i
+                        && !self.is_forbidden(k, j)
+                        && self.supplies[k] >= self.demands[j]
+                        && (0..columns)
+                            .filter(|&l| l != j && !problem.is_forbidden(k, l))
+                            .all(|l| {
+                                problem.costs[i][j] + problem.costs[k][l]
+                                    > problem.costs[i][l] + problem.costs[k][j]
+                            })
+                });
+                if dominated {
+                    routes.push((i, j));
+                }
+            }
+        }
+
+        routes
+    }
+
+    // Самый дешевый поставщик для каждого потребителя без учета запасов;
+    // запрещенные маршруты выбираются, только если других нет
+    pub fn cheapest_supplier_per_consumer(&self) -> Vec<usize> {
+        (0..self.demands.len())
+            .map(|j| {
+                (0..self.supplies.len())
+                    .min_by_key(|&i| self.costs[i][j])
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    // Вынужденные маршруты: для потребителя самый дешевый поставщик дешевле
+    // следующего хотя бы на threshold. Потребитель с единственным поставщиком
+    // тоже считается обслуживаемым вынужденно
+    pub fn forced_routes(&self, threshold: i32) -> Vec<(usize, usize)> {
+        let m = self.supplies.len();
+        let mut routes = Vec::new();
+
+        for j in 0..self.demands.len() {
+            let mut order: Vec<usize> = (0..m).collect();
+            order.sort_by_key(|&i| self.costs[i][j]);
+            let forced = match order.as_slice() {
+                [] => false,
+                [_] => true,
+                [best, next, ..] => self.costs[*next][j] - self.costs[*best][j] >= threshold,
+            };
+            if forced {
+                routes.push((order[0], j));
+            }
+        }
+
+        routes
+    }
+
+    // Все поставщики с наименьшей стоимостью до потребителя j (при равенстве
+    // стоимостей их несколько); пусто, если j вне диапазона
+    pub fn tied_cheapest_suppliers(&self, j: usize) -> Vec<usize> {
+        if j >= self.demands.len() {
+            return Vec::new();
+        }
+        let Some(min) = (0..self.supplies.len()).map(|i| self.costs[i][j]).min() else {
+            return Vec::new();
+        };
+        (0..self.supplies.len())
+            .filter(|&i| self.costs[i][j] == min)
+            .collect()
+    }
+
+    // Нижняя граница стоимости: каждый потребитель целиком получает груз от
+    // самого дешевого для него поставщика без учета запасов
+    pub fn unconstrained_minimum(&self) -> i32 {
+        self.cheapest_supplier_per_consumer()
+            .iter()
+            .enumerate()
+            .map(|(j, &i)| self.demands[j] * self.costs[i][j])
+            .sum()
+    }
+
+    // Стоимость перевозок по каждому поставщику
+    pub fn supplier_costs(&self, plan: &TransportPlan) -> Vec<i32> {
+        plan.allocations
+            .iter()
+            .zip(&self.costs)
+            .map(|(row, costs)| row.iter().zip(costs).map(|(x, c)| x * c).sum())
+            .collect()
+    }
+
+    // Стоимость перевозок по каждому потребителю
+    pub fn consumer_costs(&self, plan: &TransportPlan) -> Vec<i32> {
+        (0..self.demands.len())
+            .map(|j| {
+                (0..self.supplies.len())
+                    .map(|i| plan.allocations[i][j] * self.costs[i][j])
+                    .sum()
+            })
+            .collect()
+    }
+
+    // Стоимость перевозок по парам регионов (регион поставщика, регион
+    // потребителя); в отображение попадают только пары с перевозками.
+    // Списки регионов должны быть по одному на поставщика и потребителя
+    pub fn regional_costs(
+        &self,
+        plan: &TransportPlan,
+        supplier_regions: &[usize],
+        consumer_regions: &[usize],
+    ) -> Result<HashMap<(usize, usize), i32>, TransportError> {
+        if supplier_regions.len() != self.supplies.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.supplies.len(),
+                found: supplier_regions.len(),
+            });
+        }
+        if consumer_regions.len() != self.demands.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.demands.len(),
+                found: consumer_regions.len(),
+            });
+        }
+
+        let mut totals = HashMap::new();
+        for (i, &supplier_region) in supplier_regions.iter().enumerate() {
+            for (j, &consumer_region) in consumer_regions.iter().enumerate() {
+                let allocation = plan.allocations[i][j];
+                if allocation > 0 {
+                    *totals
+                        .entry((supplier_region, consumer_region))
+                        .or_insert(0) += allocation * self.costs[i][j];
+                }
+            }
+        }
+        Ok(totals)
+    }
+
+    // Стоимость доставки одному потребителю j
+    pub fn consumer_total_cost(
+        &self,
+        plan: &TransportPlan,
+        j: usize,
+    ) -> Result<i32, TransportError> {
+        if j >= self.demands.len() {
+            return Err(TransportError::IndexOutOfRange {
+                index: j,
+                len: self.demands.len(),
+            });
+        }
+        Ok((0..self.supplies.len())
+            .map(|i| plan.allocations[i][j] * self.costs[i][j])
+            .sum())
+    }
+
+    // Неравномерность загрузки поставщиков: коэффициент вариации (стандартное
+    // отклонение, деленное на среднее) отгруженных объемов. Отгрузки
+    // фиктивному потребителю не учитываются; 0 — загрузка одинакова
+    pub fn load_balance_index(&self, plan: &TransportPlan) -> f64 {
+        let n = self.demands.len();
+        let shipped: Vec<f64> = (0..self.supplies.len())
+            .map(|i| plan.allocations[i][..n].iter().sum::<i32>() as f64)
+            .collect();
+
+        let mean = shipped.iter().sum::<f64>() / shipped.len() as f64;
+        if mean == 0.0 {
+            return 0.0;
+        }
+        let variance =
+            shipped.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / shipped.len() as f64;
+        variance.sqrt() / mean
+    }
+
+    // Уровень обслуживания: доля суммарной потребности, закрытой реальными
+    // поставщиками. Отгрузки фиктивного поставщика — это недопоставка;
+    // значение меньше 1.0 означает дефицит
+    pub fn fill_rate(&self, plan: &TransportPlan) -> f64 {
+        let total_demand: i32 = self.demands.iter().sum();
+        if total_demand == 0 {
+            return 1.0;
+        }
+        let n = self.demands.len();
+        let satisfied: i32 = (0..self.supplies.len())
+            .map(|i| plan.allocations[i][..n].iter().sum::<i32>())
+            .sum();
+        satisfied as f64 / total_demand as f64
+    }
+
+    // Доли поставщиков в снабжении каждого потребителя: перевозка в
+    // процентах от его потребности. При дефиците сумма по столбцу меньше
+    // 100 — остаток приходится на недопоставку; у потребителя с нулевой
+    // потребностью все доли равны 0
+    pub fn plan_as_percentages(&self, plan: &TransportPlan) -> Vec<Vec<f64>> {
+        (0..self.supplies.len())
+            .map(|i| {
+                self.demands
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &demand)| match demand {
+                        0 => 0.0,
+                        demand => plan.allocations[i][j] as f64 / demand as f64 * 100.0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Средняя стоимость единицы груза по поставщику (None, если он ничего не
+    // отгружает); отгрузки фиктивному потребителю не учитываются
+    pub fn supplier_avg_cost(&self, plan: &TransportPlan) -> Vec<Option<f64>> {
+        self.supplier_costs(plan)
+            .iter()
+            .zip(&plan.allocations)
+            .map(|(&cost, row)| {
+                let shipped: i32 = row[..self.demands.len()].iter().sum();
+                if shipped == 0 {
+                    None
+                } else {
+                    Some(cost as f64 / shipped as f64)
+                }
+            })
+            .collect()
+    }
+
+    // Средняя стоимость единицы, доставленной потребителю; None, если ему
+    // ничего не доставлено. Недостача от фиктивного поставщика не учитывается
+    pub fn consumer_avg_cost(&self, plan: &TransportPlan) -> Vec<Option<f64>> {
+        self.consumer_costs(plan)
+            .iter()
+            .enumerate()
+            .map(|(j, &cost)| {
+                let delivered: i32 = (0..self.supplies.len())
+                    .map(|i| plan.allocations[i][j])
+                    .sum();
+                if delivered == 0 {
+                    None
+                } else {
+                    Some(cost as f64 / delivered as f64)
+                }
+            })
+            .collect()
+    }
+
+    // Потребитель с наибольшей средней стоимостью доставки единицы и эта
+    // стоимость; при равенстве — первый. (0, 0.0), если доставок нет
+    pub fn most_expensive_consumer(&self, plan: &TransportPlan) -> (usize, f64) {
+        self.consumer_avg_cost(plan)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(j, cost)| cost.map(|cost| (j, cost)))
+            .fold(None, |best: Option<(usize, f64)>, (j, cost)| match best {
+                Some((_, best_cost)) if best_cost >= cost => best,
+                _ => Some((j, cost)),
+            })
+            .unwrap_or((0, 0.0))
+    }
+
+    // Грузооборот (тонно-километры), если стоимости — расстояния. Совпадает
+    // с calculate_total_cost, но без фиксированных доплат за маршруты
+    pub fn ton_kilometers(&self, plan: &TransportPlan) -> i32 {
+        plan.occupied_cells()
+            .map(|(i, j, amount)| amount * self.costs[i][j])
+            .sum()
+    }
+
+    // Доля каждого используемого маршрута в стоимости перевозок, в процентах,
+    // по убыванию. При нулевой стоимости все доли равны нулю
+    pub fn cost_contributions(&self, plan: &TransportPlan) -> Vec<(usize, usize, f64)> {
+        let total = self.ton_kilometers(plan);
+        let mut contributions: Vec<(usize, usize, f64)> = plan
+            .occupied_cells()
+            .map(|(i, j, amount)| {
+                let share = if total == 0 {
+                    0.0
+                } else {
+                    (amount * self.costs[i][j]) as f64 / total as f64 * 100.0
+                };
+                (i, j, share)
+            })
+            .collect();
+
+        contributions.sort_by(|a, b| b.2.total_cmp(&a.2));
+        contributions
+    }
+
+    // Общий объем перевезенного груза
+    pub fn total_units(&self, plan: &TransportPlan) -> i32 {
+        plan.occupied_cells().map(|(_, _, amount)| amount).sum()
+    }
+
+    // Средняя дальность перевозки: грузооборот на единицу груза
+    // (None для пустого плана)
+    pub fn average_haul(&self, plan: &TransportPlan) -> Option<f64> {
+        let units = self.total_units(plan);
+        if units == 0 {
+            None
+        } else {
+            Some(self.ton_kilometers(plan) as f64 / units as f64)
+        }
+    }
+
+    // Гистограмма объемов по занятым клеткам: (нижняя граница, верхняя
+    // граница включительно, число клеток). Интервалы одинаковой ширины
+    // покрывают диапазон от наименьшего до наибольшего объема; их не больше
+    // buckets, пустые интервалы в середине сохраняются
+    pub fn allocation_histogram(
+        &self,
+        plan: &TransportPlan,
+        buckets: usize,
+    ) -> Vec<(i32, i32, usize)> {
+        let sizes: Vec<i32> = plan.occupied_cells().map(|(_, _, amount)| amount).collect();
+        let (Some(&min), Some(&max)) = (sizes.iter().min(), sizes.iter().max()) else {
+            return Vec::new();
+        };
+        if buckets == 0 {
+            return Vec::new();
+        }
+
+        let width = ((max - min + 1) as usize).div_ceil(buckets) as i32;
+        let count = ((max - min) / width + 1) as usize;
+        let mut histogram: Vec<(i32, i32, usize)> = (0..count as i32)
+            .map(|k| (min + k * width, min + (k + 1) * width - 1, 0))
+            .collect();
+        for size in sizes {
+            histogram[((size - min) / width) as usize].2 += 1;
+        }
+
+        histogram
+    }
+
+    // Ближайшая к оптимуму стоимость: наименьшая строго большая стоимость,
+    // достижимая одним поворотом через небазисную клетку оптимального плана.
+    // None, если любой поворот оставляет стоимость прежней
+    pub fn second_best_cost(&self) -> Option<i32> {
+        let problem = self.balanced();
+        let optimum = problem.solve_assume_balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+        let mut second: Option<i32> = None;
+
+        for i in 0..m {
+            for j in 0..n {
+                if optimum.allocations[i][j] > 0 {
+                    continue;
+                }
+                let mut allocations = optimum.allocations.clone();
+                if problem.pivot(&mut allocations, i, j).is_some() {
+                    let cost = problem.calculate_total_cost(&allocations);
+                    if cost > optimum.total_cost && second.is_none_or(|c| cost < c) {
+                        second = Some(cost);
+                    }
+                }
+            }
+        }
+
+        second
+    }
+
+    // Число занятых клеток плана
+    pub fn basis_size(&self, plan: &TransportPlan) -> usize {
+        plan.allocations
+            .iter()
+            .flatten()
+            .filter(|&&allocation| allocation != 0)
+            .count()
+    }
+
+    // Степень вырожденности: сколько занятых клеток не хватает до базиса
+    // m + n - 1. Ноль — обычный базис, отрицательное значение — лишние клетки
+    pub fn degeneracy(&self, plan: &TransportPlan) -> i32 {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        (m + n) as i32 - 1 - self.basis_size(plan) as i32
+    }
+
+    // Проверка плана: размеры, баланс строк и столбцов, знаки и размер базиса
+    pub fn validate_plan(&self, plan: &TransportPlan) -> Vec<PlanIssue> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let rows = plan.allocations.rows();
+        let cols = plan.allocations.cols();
+
+        if rows != m || cols != n {
+            return vec![PlanIssue::Dimensions {
+                expected: (m, n),
+                found: (rows, cols),
+            }];
+        }
+
+        let mut issues = Vec::new();
+
+        for i in 0..m {
+            let found = plan.allocations.row_sum(i);
+            if found != self.supplies[i] {
+                issues.push(PlanIssue::RowSum {
+                    row: i,
+                    expected: self.supplies[i],
+                    found,
+                });
+            }
+        }
+        for j in 0..n {
+            let found = plan.allocations.col_sum(j);
+            if found != self.demands[j] {
+                issues.push(PlanIssue::ColumnSum {
+                    column: j,
+                    expected: self.demands[j],
+                    found,
+                });
+            }
+        }
+        for i in 0..m {
+            for j in 0..n {
+                if plan.allocations[i][j] < 0 {
+                    issues.push(PlanIssue::Negative {
+                        i,
+                        j,
+                        value: plan.allocations[i][j],
+                    });
+                }
+            }
+        }
+
+        let expected = (m + n).saturating_sub(1);
+        let found = self.basis_size(plan);
+        if found > expected {
+            issues.push(PlanIssue::TooManyRoutes { expected, found });
+        } else if found < expected {
+            issues.push(PlanIssue::Degenerate { expected, found });
+        }
+
+        issues
+    }
+
+    // Округление дробного плана (например, полученного внешним LP-решателем)
+    // до целого с сохранением баланса строк и столбцов. Каждая клетка
+    // округляется вниз или вверх: недостающие единицы раздаются клеткам с
+    // дробной частью по увеличивающим путям. Возвращает целый план и
+    // изменение стоимости относительно дробного
+    pub fn round_to_integer(
+        &self,
+        fractional: &[Vec<f64>],
+    ) -> Result<(TransportPlan, f64), TransportError> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        if fractional.len() != m {
+            return Err(TransportError::DimensionMismatch {
+                expected: m,
+                found: fractional.len(),
+            });
+        }
+        for (i, row) in fractional.iter().enumerate() {
+            if row.len() != n {
+                return Err(TransportError::RaggedRow {
+                    row: i,
+                    expected: n,
+                    found: row.len(),
+                });
+            }
+        }
+
+        let mut negative = Vec::new();
+        for (i, row) in fractional.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                if value < -EPSILON {
+                    negative.push((i, j));
+                }
+            }
+        }
+        if !negative.is_empty() {
+            return Err(TransportError::NegativeAllocation { cells: negative });
+        }
+
+        // Округление вниз; клетки с дробной частью можно поднять на единицу
+        let mut allocations = Matrix::zeros(m, n);
+        let mut open = vec![vec![false; n]; m];
+        for i in 0..m {
+            for j in 0..n {
+                let value = fractional[i][j].max(0.0);
+                let floor = (value + EPSILON).floor();
+                allocations[i][j] = floor as i32;
+                open[i][j] = value - floor > EPSILON;
+            }
+        }
+
+        let mut row_need: Vec<i32> = (0..m)
+            .map(|i| self.supplies[i] - allocations.row_sum(i))
+            .collect();
+        let mut col_need: Vec<i32> = (0..n)
+            .map(|j| self.demands[j] - allocations.col_sum(j))
+            .collect();
+        if row_need.iter().chain(&col_need).any(|&need| need < 0)
+            || row_need.iter().sum::<i32>() != col_need.iter().sum::<i32>()
+        {
+            return Err(TransportError::Infeasible);
+        }
+
+        let mut raised = vec![vec![false; n]; m];
+        for i in 0..m {
+            while row_need[i] > 0 {
+                let mut visited = vec![false; n];
+                if !Self::raise_cell(i, &open, &mut raised, &mut col_need, &mut visited) {
+                    return Err(TransportError::Infeasible);
+                }
+                row_need[i] -= 1;
+            }
+        }
+        for i in 0..m {
+            for j in 0..n {
+                if raised[i][j] {
+                    allocations[i][j] += 1;
+                }
+            }
+        }
+
+        let mut fractional_cost = 0.0;
+        for i in 0..m {
+            for j in 0..n {
+                fractional_cost += fractional[i][j] * self.costs[i][j] as f64;
+                if let Some(fixed) = &self.fixed_costs
+                    && fractional[i][j] > EPSILON
+                {
+                    fractional_cost += fixed[i][j] as f64;
+                }
+            }
+        }
+
+        let total_cost = self.calculate_total_cost(&allocations);
+        let plan = TransportPlan {
+            allocations,
+            total_cost,
+        };
+        Ok((plan, total_cost as f64 - fractional_cost))
+    }
+
+    // Увеличивающий путь для округления: строка -> открытая клетка -> столбец.
+    // Если у столбца нет недостачи, путь продолжается через уже поднятую
+    // клетку этого столбца в другую строку, которая переносит подъем
+    fn raise_cell(
+        i: usize,
+        open: &[Vec<bool>],
+        raised: &mut [Vec<bool>],
+        col_need: &mut [i32],
+        visited: &mut [bool],
+    ) -> bool {
+        for j in 0..col_need.len() {
+            if !open[i][j] || raised[i][j] || visited[j] {
+                continue;
+            }
+            visited[j] = true;
+            if col_need[j] > 0 {
+                col_need[j] -= 1;
+                raised[i][j] = true;
+                return true;
+            }
+            for k in 0..raised.len() {
+                if raised[k][j] && Self::raise_cell(k, open, raised, col_need, visited) {
+                    raised[k][j] = false;
+                    raised[i][j] = true;
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Объединение поставщиков с одинаковыми строками стоимостей (и
+    // фиксированных доплат) в одного с суммарным запасом. Второй элемент —
+    // для каждой строки новой задачи исходные индексы, по ним expand_plan
+    // восстанавливает план исходной задачи. Строки с ограничениями
+    // пропускной способности не объединяются
+    pub fn coalesce_identical_suppliers(&self) -> (TransportProblem, Vec<Vec<usize>>) {
+        let limited = |i: usize| {
+            self.route_capacities
+                .as_ref()
+                .is_some_and(|capacities| capacities[i].iter().any(|&c| c > 0))
+        };
+        let same = |a: usize, b: usize| {
+            self.costs[a] == self.costs[b]
+                && self
+                    .fixed_costs
+                    .as_ref()
+                    .is_none_or(|fixed| fixed[a] == fixed[b])
+        };
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in 0..self.supplies.len() {
+            let group = groups
+                .iter_mut()
+                .find(|group| !limited(i) && !limited(group[0]) && same(group[0], i));
+            match group {
+                Some(group) => group.push(i),
+                None => groups.push(vec![i]),
+            }
+        }
+
+        let mut reduced = self.clone();
+        reduced.supplies = groups
+            .iter()
+            .map(|group| group.iter().map(|&i| self.supplies[i]).sum())
+            .collect();
+        // Строка первого поставщика группы представляет всю группу
+        fn first_rows(matrix: &Matrix, groups: &[Vec<usize>]) -> Matrix {
+            let rows: Vec<Vec<i32>> = groups
+                .iter()
+                .map(|group| matrix[group[0]].to_vec())
+                .collect();
+            Matrix::try_from(rows).unwrap()
+        }
+        reduced.costs = first_rows(&self.costs, &groups);
+        reduced.fixed_costs = self.fixed_costs.as_ref().map(|m| first_rows(m, &groups));
+        reduced.route_capacities = self
+            .route_capacities
+            .as_ref()
+            .map(|m| first_rows(m, &groups));
+        reduced.supplier_names = self.supplier_names.as_ref().map(|_| {
+            groups
+                .iter()
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|&i| self.supplier_label(i))
+                        .collect::<Vec<_>>()
+                        .join("+")
+                })
+                .collect()
+        });
+
+        (reduced, groups)
+    }
+
+    // Раскрытие плана объединенной задачи: перевозки объединенного
+    // поставщика распределяются между исходными по порядку в пределах их
+    // запасов. Стоимости перевозок при этом не меняются, фиксированные
+    // доплаты могут вырасти, если маршрут достался нескольким поставщикам.
+    // Строки плана сверх groups (фиктивный поставщик) переносятся как есть
+    pub fn expand_plan(&self, plan: &TransportPlan, groups: &[Vec<usize>]) -> TransportPlan {
+        let m = self.supplies.len();
+        let cols = plan.allocations.cols();
+        let extra = plan.allocations.rows() - groups.len();
+        let mut allocations = Matrix::zeros(m + extra, cols);
+
+        for (row, group) in groups.iter().enumerate() {
+            let mut remaining: Vec<i32> = group.iter().map(|&i| self.supplies[i]).collect();
+            for j in 0..cols {
+                let mut amount = plan.allocations[row][j];
+                for (k, &i) in group.iter().enumerate() {
+                    let part = amount.min(remaining[k]);
+                    allocations[i][j] += part;
+                    remaining[k] -= part;
+                    amount -= part;
+                }
+            }
+        }
+        for k in 0..extra {
+            for j in 0..cols {
+                allocations[m + k][j] = plan.allocations[groups.len() + k][j];
+            }
+        }
+
+        let mut expanded = TransportPlan {
+            allocations,
+            total_cost: 0,
+        };
+        expanded.recompute_cost(&self.balanced());
+        expanded
+    }
+
+    // Оптимальная стоимость, если поставщики a и b обменяются строками
+    // стоимостей (вместе с доплатами и пропускной способностью) и запасами.
+    // Индекс вне 0..supplies.len() вызывает панику
+    pub fn cost_with_swapped_suppliers(&self, a: usize, b: usize) -> i32 {
+        let m = self.supplies.len();
+        assert!(a < m && b < m, "индекс поставщика вне диапазона 0..{}", m);
+
+        let mut swapped = self.clone();
+        swapped.supplies.swap(a, b);
+        swapped.costs.swap_rows(a, b);
+        if let Some(fixed) = &mut swapped.fixed_costs {
+            fixed.swap_rows(a, b);
+        }
+        if let Some(capacities) = &mut swapped.route_capacities {
+            capacities.swap_rows(a, b);
+        }
+        swapped.solve_to_plan().total_cost
+    }
+
+    // Оптимальная стоимость с дополнительным гипотетическим поставщиком
+    pub fn evaluate_new_supplier(
+        &self,
+        capacity: i32,
+        costs_to_consumers: Vec<i32>,
+    ) -> Result<i32, TransportError> {
+        if costs_to_consumers.len() != self.demands.len() {
+            return Err(TransportError::DimensionMismatch {
+                expected: self.demands.len(),
+                found: costs_to_consumers.len(),
+            });
+        }
+
+        let mut expanded = self.clone();
+        expanded.supplies.push(capacity);
+        expanded.costs.push_row(costs_to_consumers);
+        if let Some(fixed) = &mut expanded.fixed_costs {
+            fixed.push_row(vec![0; self.demands.len()]);
+        }
+        if let Some(capacities) = &mut expanded.route_capacities {
+            capacities.push_row(vec![0; self.demands.len()]);
+        }
+        Ok(expanded.solve_to_plan().total_cost)
+    }
+
+    // Оптимальная стоимость при выходе из строя поставщика i: его запас и
+    // маршруты исключаются. Infeasible, если оставшиеся запасы не покрывают
+    // потребностей
+    pub fn cost_without_supplier(&self, i: usize) -> Result<i32, TransportError> {
+        if i >= self.supplies.len() {
+            return Err(TransportError::IndexOutOfRange {
+                index: i,
+                len: self.supplies.len(),
+            });
+        }
+
+        let mut reduced = self.clone();
+        reduced.supplies.remove(i);
+        reduced.costs.remove_row(i);
+        if let Some(names) = &mut reduced.supplier_names {
+            names.remove(i);
+        }
+        if let Some(fixed) = &mut reduced.fixed_costs {
+            fixed.remove_row(i);
+        }
+        if let Some(capacities) = &mut reduced.route_capacities {
+            capacities.remove_row(i);
+        }
+
+        if reduced.supplies.iter().sum::<i32>() < reduced.demands.iter().sum::<i32>() {
+            return Err(TransportError::Infeasible);
+        }
+        Ok(reduced.solve_to_plan().total_cost)
+    }
+
+    // Маршрут, снижение стоимости которого на reduction (не ниже нуля) сильнее
+    // всего уменьшает оптимум: (i, j, новая оптимальная стоимость). Задача
+    // перерешивается для каждого из m·n маршрутов, поэтому маршруты
+    // разбиваются между потоками. При равенстве выбирается первый маршрут
+    // в построчном порядке; None, если маршрутов нет
+    pub fn most_valuable_cost_reduction(&self, reduction: i32) -> Option<(usize, usize, i32)> {
+        let n = self.demands.len();
+        let routes: Vec<(usize, usize)> = (0..self.supplies.len())
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .collect();
+        let results = parallel_map(&routes, |&(i, j)| {
+            let mut problem = self.clone();
+            problem.costs[i][j] = (problem.costs[i][j] - reduction).max(0);
+            (i, j, problem.solve_to_plan().total_cost)
+        });
+
+        results.into_iter().min_by_key(|&(i, j, cost)| (cost, i, j))
+    }
+
+    // Нехватка запасов и поставщик, которого дешевле всего расширить: тот,
+    // у кого средняя стоимость доставки недостающих объемов минимальна.
+    // Недостающие объемы — перевозки фиктивного поставщика в оптимуме.
+    // Если запасов хватает, возвращается (0, 0)
+    pub fn min_supply_to_balance(&self) -> (i32, usize) {
+        let shortfall = self.demands.iter().sum::<i32>() - self.supplies.iter().sum::<i32>();
+        if shortfall <= 0 {
+            return (0, 0);
+        }
+
+        let plan = self.solve_to_plan();
+        let unmet = &plan.allocations[self.supplies.len()];
+        let cheapest = (0..self.supplies.len())
+            .min_by_key(|&i| {
+                (0..self.demands.len())
+                    .map(|j| unmet[j] as i64 * self.costs[i][j] as i64)
+                    .sum::<i64>()
+            })
+            .unwrap_or(0);
+
+        (shortfall, cheapest)
+    }
+
+    // Предельная стоимость объема: задача перерешивается с суммарной
+    // потребностью, увеличенной на delta пропорционально потребностям, и
+    // возвращается (новый оптимум - оптимум) / delta. Если запасов перестает
+    // хватать, они тоже пропорционально увеличиваются, иначе недостача
+    // ушла бы бесплатному фиктивному поставщику
+    pub fn demand_elasticity(&self, delta: i32) -> f64 {
+        if delta == 0 {
+            return 0.0;
+        }
+
+        let total_demand = self.demands.iter().sum::<i32>() + delta;
+        let mut scaled = self.clone();
+        scaled.demands = scale_proportionally(&self.demands, total_demand);
+        if self.supplies.iter().sum::<i32>() < total_demand {
+            scaled.supplies = scale_proportionally(&self.supplies, total_demand);
+        }
+
+        let optimum = self.solve_to_plan().total_cost;
+        let new_optimum = scaled.solve_to_plan().total_cost;
+        (new_optimum - optimum) as f64 / delta as f64
+    }
+
+    // Критический маршрут: используемый в плане маршрут, запрет которого
+    // сильнее всего повышает оптимум, — (i, j, прирост стоимости). Если без
+    // маршрута задача становится недопустимой, прирост равен i32::MAX.
+    // Перерешение выполняется для каждой занятой клетки плана, поэтому для
+    // больших задач это число решений порядка m + n. Для пустого плана (0, 0, 0)
+    pub fn most_critical_route(&self, plan: &TransportPlan) -> (usize, usize, i32) {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let optimum = self.solve_to_plan().total_cost;
+
+        plan.occupied_cells()
+            .filter(|&(i, j, _)| i < m && j < n && !self.is_forbidden(i, j))
+            .map(|(i, j, _)| {
+                let mut blocked = self.clone();
+                blocked.costs[i][j] = FORBIDDEN;
+                let resolved = blocked.solve_to_plan();
+                let infeasible = resolved
+                    .occupied_cells()
+                    .any(|(ri, rj, _)| ri < m && rj < n && blocked.is_forbidden(ri, rj));
+                let increase = if infeasible {
+                    i32::MAX
+                } else {
+                    resolved.total_cost - optimum
+                };
+                (i, j, increase)
+            })
+            .max_by_key(|&(i, j, increase)| (increase, std::cmp::Reverse((i, j))))
+            .unwrap_or((0, 0, 0))
+    }
+
+    // Загрузка маршрутов: перевозка, деленная на пропускную способность;
+    // 0 для маршрутов без ограничения. Значение 1.0 — маршрут насыщен
+    pub fn route_utilization(&self, plan: &TransportPlan) -> Vec<Vec<f64>> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let mut utilization = vec![vec![0.0; n]; m];
+
+        if let Some(capacities) = &self.route_capacities {
+            for i in 0..m {
+                for j in 0..n {
+                    if capacities[i][j] > 0 {
+                        utilization[i][j] = plan.allocations[i][j] as f64 / capacities[i][j] as f64;
+                    }
+                }
+            }
+        }
+        utilization
+    }
+
+    // Насыщенные маршруты: загрузка достигла пропускной способности
+    pub fn saturated_routes(&self, plan: &TransportPlan) -> Vec<(usize, usize)> {
+        let utilization = self.route_utilization(plan);
+        let mut routes = Vec::new();
+        for (i, row) in utilization.iter().enumerate() {
+            for (j, &value) in row.iter().enumerate() {
+                if value >= 1.0 - EPSILON {
+                    routes.push((i, j));
+                }
+            }
+        }
+        routes
+    }
+
+    // n самых малых перевозок — кандидаты на объединение
+    pub fn least_used_routes(&self, plan: &TransportPlan, n: usize) -> Vec<(usize, usize, i32)> {
+        let mut routes: Vec<_> = plan.occupied_cells().collect();
+        routes.sort_by_key(|&(i, j, allocation)| (allocation, i, j));
+        routes.truncate(n);
+        routes
+    }
+
+    // Предельная ценность мощности поставщиков: изменение оптимума при
+    // увеличении запаса i на единицу; план — для сбалансированной задачи
+    pub fn supply_marginals(&self, plan: &TransportPlan) -> Vec<f64> {
+        let problem = self.balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+
+        (0..self.supplies.len())
+            .map(|i| {
+                let mut u = vec![f64::INFINITY; m];
+                u[i] = 0.0;
+                let (u, v) = problem.residual_distances(plan, u, vec![f64::INFINITY; n]);
+                if n > self.demands.len() {
+                    v[n - 1]
+                } else if m > self.supplies.len() {
+                    u[m - 1]
+                } else {
+                    u.iter().copied().fold(f64::INFINITY, f64::min)
+                }
+            })
+            .collect()
+    }
+
+    // Теневая цена потребителя j: изменение оптимума при росте его потребности
+    // на единицу; f64::INFINITY, если j недостижим без запрещенных маршрутов
+    pub fn marginal_cost_to_consumer(
+        &self,
+        plan: &TransportPlan,
+        j: usize,
+    ) -> Result<f64, TransportError> {
+        if j >= self.demands.len() {
+            return Err(TransportError::IndexOutOfRange {
+                index: j,
+                len: self.demands.len(),
+            });
+        }
+
+        let problem = self.balanced();
+        let n = problem.demands.len();
+        let mut v = vec![f64::INFINITY; n];
+        if n > self.demands.len() {
+            v[n - 1] = 0.0;
+        } else {
+            v.fill(0.0);
+        }
+        let u = vec![f64::INFINITY; problem.supplies.len()];
+        let (_, v) = problem.residual_distances(plan, u, v);
+
+        Ok(v[j])
+    }
+
+    // Кратчайшие пути по остаточной сети плана сбалансированной задачи:
+    // новая перевозка i -> j стоит c_ij, снятие занятой — -c_ij
+    fn residual_distances(
+        &self,
+        plan: &TransportPlan,
+        mut u: Vec<f64>,
+        mut v: Vec<f64>,
+    ) -> (Vec<f64>, Vec<f64>) {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+
+        // Без отрицательных циклов (план оптимален) хватает m + n проходов
+        for _ in 0..m + n {
+            let mut changed = false;
+            for i in 0..m {
+                for k in 0..n {
+                    let cost = self.costs[i][k] as f64;
+                    if plan.allocations[i][k] > 0 && v[k] - cost < u[i] {
+                        u[i] = v[k] - cost;
+                        changed = true;
+                    }
+                    if !self.is_forbidden(i, k) && u[i] + cost < v[k] {
+                        v[k] = u[i] + cost;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        (u, v)
+    }
+
+    // «Сожаление» по маршрутам: оценка c_ij - u_i - v_j для свободных клеток
+    // (рост стоимости на единицу груза), 0 для занятых. Ошибка, если базис
+    // плана не удается сделать связным
+    pub fn route_regret(&self, plan: &TransportPlan) -> Result<Vec<Vec<f64>>, PotentialError> {
+        let problem = self.balanced();
+        let mut basis = problem.optimal_basis(&plan.allocations);
+        let (u, v) = problem.repaired_potentials(&mut basis, &plan.allocations)?;
+
+        Ok((0..self.supplies.len())
+            .map(|i| {
+                (0..self.demands.len())
+                    .map(|j| {
+                        if plan.allocations[i][j] > 0 {
+                            0.0
+                        } else {
+                            self.reduced_cost(i, j, &u, &v)
+                        }
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+
+    // Все клетки, ввод которых в базис улучшил бы план: небазисные клетки
+    // сбалансированной задачи с отрицательной оценкой, от самой
+    // отрицательной. Базис вырожденного плана сначала очищается поворотами
+    // с нулевым объемом (как в route_regret), поэтому у оптимума список пуст
+    pub fn improving_cells(&self, plan: &TransportPlan) -> Vec<(usize, usize, f64)> {
+        let problem = self.balanced();
+        let basis = problem.optimal_basis(&plan.allocations);
+        let Ok((u, v)) = problem.potentials_for_basis(&basis) else {
+            return Vec::new();
+        };
+
+        let mut cells = Vec::new();
+        for i in 0..problem.supplies.len() {
+            for j in 0..problem.demands.len() {
+                let delta = problem.reduced_cost(i, j, &u, &v);
+                if !basis[i][j] && delta <= -EPSILON {
+                    cells.push((i, j, delta));
+                }
+            }
+        }
+        cells.sort_by(|a, b| a.2.total_cmp(&b.2));
+        cells
+    }
+
+    // Наименьшая оценка среди небазисных клеток (базис очищен, как в
+    // improving_cells). У оптимума она неотрицательна; ноль означает
+    // альтернативный оптимум, большое значение — любое отклонение от плана
+    // заметно дороже. Если небазисных клеток нет, f64::INFINITY
+    pub fn min_reduced_cost(&self, plan: &TransportPlan) -> f64 {
+        let problem = self.balanced();
+        let basis = problem.optimal_basis(&plan.allocations);
+        let Ok((u, v)) = problem.potentials_for_basis(&basis) else {
+            return f64::INFINITY;
+        };
+
+        let mut smallest = f64::INFINITY;
+        for i in 0..problem.supplies.len() {
+            for j in 0..problem.demands.len() {
+                if !basis[i][j] {
+                    smallest = smallest.min(problem.reduced_cost(i, j, &u, &v));
+                }
+            }
+        }
+        smallest
+    }
+
+    // Единственность оптимума: все небазисные оценки оптимального плана
+    // строго положительны. Условие достаточное: у вырожденного оптимума
+    // нулевая оценка возможна и без другого плана, тогда ответ false
+    // осторожен; точнее альтернативы ищет solve_full
+    pub fn has_unique_optimum(&self) -> bool {
+        self.min_reduced_cost(&self.solve_to_plan()) > EPSILON
+    }
+
+    // Пояснение к оптимуму: неиспользуемые разрешенные маршруты от самого
+    // «безобидного» к самому дорогому с ценой отказа от плана
+    pub fn write_route_regret(&self, w: &mut dyn Write, plan: &TransportPlan) -> io::Result<()> {
+        let regret = self
+            .route_regret(plan)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        let mut routes = Vec::new();
+        for i in 0..self.supplies.len() {
+            for j in 0..self.demands.len() {
+                if plan.allocations[i][j] == 0 && !self.is_forbidden(i, j) {
+                    routes.push((i, j, regret[i][j]));
+                }
+            }
+        }
+        routes.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        for (i, j, value) in routes {
+            let route = format!("{} -> {}", self.supplier_label(i), self.consumer_label(j));
+            if value.abs() < EPSILON {
+                writeln!(
+                    w,
+                    "{}: альтернативный маршрут, стоимость не изменится",
+                    route
+                )?;
+            } else if value > 0.0 {
+                writeln!(
+                    w,
+                    "{}: каждая единица по этому маршруту обойдется дороже на {:.2} {}",
+                    route, value, self.unit
+                )?;
+            } else {
+                writeln!(
+                    w,
+                    "{}: маршрут выгоднее плана на {:.2} {} за единицу — план не оптимален",
+                    route, -value, self.unit
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // Двойственная задача: потенциалы u, v оптимального плана и значение
+    // sum(a_i * u_i) + sum(b_j * v_j), по теореме двойственности равное
+    // оптимуму прямой задачи. Считается по сбалансированной задаче, поэтому
+    // у открытой задачи в u или v есть потенциал фиктивного участника
+    pub fn solve_dual(&self) -> Result<(Vec<f64>, Vec<f64>, f64), PotentialError> {
+        let problem = self.balanced();
+        let optimum = problem.solve_assume_balanced();
+        let mut basis = problem.optimal_basis(&optimum.allocations);
+        let (u, v) = problem.repaired_potentials(&mut basis, &optimum.allocations)?;
+
+        let objective = problem.dual_objective(&u, &v);
+        Ok((u, v, objective))
+    }
+
+    // Значение двойственной целевой функции: sum a_i u_i + sum b_j v_j
+    fn dual_objective(&self, u: &[f64], v: &[f64]) -> f64 {
+        self.supplies
+            .iter()
+            .zip(u)
+            .map(|(&a, &u_i)| a as f64 * u_i)
+            .sum::<f64>()
+            + self
+                .demands
+                .iter()
+                .zip(v)
+                .map(|(&b, &v_j)| b as f64 * v_j)
+                .sum::<f64>()
+    }
+
+    // Оптимальная стоимость при маршруте (i, j), закрепленном на объеме amount:
+    // остаток задачи решается с запрещенным маршрутом (i, j), к его стоимости
+    // добавляется стоимость закрепленной перевозки
+    pub fn optimum_with_route_fixed(
+        &self,
+        i: usize,
+        j: usize,
+        amount: i32,
+    ) -> Result<i32, TransportError> {
+        if i >= self.supplies.len() {
+            return Err(TransportError::IndexOutOfRange {
+                index: i,
+                len: self.supplies.len(),
+            });
+        }
+        if j >= self.demands.len() {
+            return Err(TransportError::IndexOutOfRange {
+                index: j,
+                len: self.demands.len(),
+            });
+        }
+        if amount < 0 || amount > self.supplies[i] || amount > self.demands[j] {
+            return Err(TransportError::Infeasible);
+        }
+
+        let mut residual = self.clone();
+        residual.supplies[i] -= amount;
+        residual.demands[j] -= amount;
+        residual.costs[i][j] = FORBIDDEN;
+
+        let balanced = residual.balanced();
+        let plan = balanced.solve_assume_balanced();
+        if plan
+            .occupied_cells()
+            .any(|(r, c, _)| balanced.is_forbidden(r, c))
+        {
+            return Err(TransportError::Infeasible);
+        }
+
+        Ok(plan.total_cost + amount * self.costs[i][j])
+    }
+
+    // Связывающие ограничения: реальные поставщики, вывозящие весь запас,
+    // и реальные потребители, получающие всю потребность. Перевозки
+    // фиктивного поставщика или потребителя в зачет не идут, поэтому
+    // остальные строки и столбцы имеют резерв
+    pub fn binding_constraints(&self, plan: &TransportPlan) -> (Vec<usize>, Vec<usize>) {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+
+        let suppliers = (0..m)
+            .filter(|&i| (0..n).map(|j| plan.allocations[i][j]).sum::<i32>() == self.supplies[i])
+            .collect();
+        let consumers = (0..n)
+            .filter(|&j| (0..m).map(|i| plan.allocations[i][j]).sum::<i32>() == self.demands[j])
+            .collect();
+
+        (suppliers, consumers)
+    }
+
+    // Эталонный план по распределению: стоимость и проверка допустимости.
+    // Вырожденность и лишние занятые клетки допустимости не нарушают;
+    // план другого размера заменяется нулевым с ошибкой размеров
+    pub fn from_reference_plan(&self, allocations: Vec<Vec<i32>>, name: &str) -> ReferencePlan {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let cols = allocations
+            .iter()
+            .map(Vec::len)
+            .find(|&len| len != n)
+            .unwrap_or(n);
+        let found = (allocations.len(), cols);
+
+        let mut plan = TransportPlan {
+            allocations: Matrix::zeros(m, n),
+            total_cost: 0,
+        };
+        let issues = match Matrix::try_from(allocations) {
+            Ok(allocations) if found == (m, n) => {
+                plan.allocations = allocations;
+                plan.recompute_cost(self);
+                self.validate_plan(&plan)
+                    .into_iter()
+                    .filter(|issue| {
+                        !matches!(
+                            issue,
+                            PlanIssue::Degenerate { .. } | PlanIssue::TooManyRoutes { .. }
+                        )
+                    })
+                    .collect()
+            }
+            _ => vec![PlanIssue::Dimensions {
+                expected: (m, n),
+                found,
+            }],
+        };
+
+        ReferencePlan {
+            name: name.to_string(),
+            plan,
+            issues,
+        }
+    }
+
+    // Анализ: стоимости именованных эталонных планов в сравнении с оптимумом
+    pub fn analyze(&self, references: &[ReferencePlan]) -> AnalysisReport {
+        let initial_plan = self.balanced().north_west_corner();
+        let optimal_plan = self.solve_to_plan();
+
+        AnalysisReport {
+            initial_cost: initial_plan.total_cost,
+            initial_spread: self.initial_cost_spread(),
+            optimal_cost: optimal_plan.total_cost,
+            worst_cost: self.worst_plan().total_cost,
+            references: references.to_vec(),
+            unit: self.unit.clone(),
+        }
+    }
+
+    // Краткая сводка в одну строку для журналов пакетной обработки, например
+    // «3x5 balanced | optimum=6520 | routes=6 | iters=3». Число итераций —
+    // по оптимизации от плана северо-западного угла
+    pub fn summary_line(&self, plan: &TransportPlan) -> String {
+        let problem = self.balanced();
+        let start = problem.north_west_corner();
+        let iterations = problem.optimize_with_callback(start, |_| true).iterations;
+
+        format!(
+            "{}x{} {} | optimum={} | routes={} | iters={}",
+            self.supplies.len(),
+            self.demands.len(),
+            if self.is_balanced() {
+                "balanced"
+            } else {
+                "unbalanced"
+            },
+            plan.total_cost,
+            self.basis_size(plan),
+            iterations
+        )
+    }
+
+    // Построчная разница планов: клетки, где объем в to отличается от from,
+    // с изменением to - from
+    pub fn diff_plans(&self, from: &TransportPlan, to: &TransportPlan) -> Vec<(usize, usize, i32)> {
+        let mut changes = Vec::new();
+        for (i, (before, after)) in from.allocations.iter().zip(&to.allocations).enumerate() {
+            for (j, (&old, &new)) in before.iter().zip(after).enumerate() {
+                if old != new {
+                    changes.push((i, j, new - old));
+                }
+            }
+        }
+        changes
+    }
+
+    // Сравнение ручного плана с оптимумом: переплата и перевозки, которые
+    // нужно изменить
+    pub fn explain_vs(&self, manual: &TransportPlan) -> GapReport {
+        let optimal = self.solve_to_plan();
+
+        GapReport {
+            manual_cost: self.calculate_total_cost(&manual.allocations),
+            optimal_cost: optimal.total_cost,
+            reallocations: self.diff_plans(manual, &optimal),
+            unit: self.unit.clone(),
+        }
+    }
+
+    // Решение задачи с выводом в stdout
+    pub fn solve(&self) {
+        // Ошибка записи в stdout игнорируется: выводить ее некуда
+        let _ = self.solve_to_writer(&mut io::stdout());
+    }
+
+    // Решение задачи с выводом в произвольный приемник: буфер, файл, тест
+    pub fn solve_to_writer(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w, "=== ТРАНСПОРТНАЯ ЗАДАЧА ===")?;
+        writeln!(w, "Запасы: {:?}", self.supplies)?;
+        writeln!(w, "Потребности: {:?}", self.demands)?;
+        writeln!(w, "Матрица стоимостей:")?;
+        for row in &self.costs {
+            writeln!(w, "{:?}", row)?;
+        }
+        writeln!(w)?;
+
+        if let Some((i, j)) = self.has_negative_costs() {
+            writeln!(
+                w,
+                "Внимание: отрицательная стоимость {} -> {}: {} (ожидаются затраты, а не прибыль)\n",
+                self.supplier_label(i),
+                self.consumer_label(j),
+                self.costs[i][j]
+            )?;
+        }
+
+        // Открытая задача решается с фиктивным поставщиком или потребителем
+        let problem = self.balanced();
+        if problem.supplies.len() > self.supplies.len() {
+            writeln!(
+                w,
+                "Задача не сбалансирована: добавлен фиктивный поставщик {}\n",
+                problem.supplier_label(self.supplies.len())
+            )?;
+        } else if problem.demands.len() > self.demands.len() {
+            writeln!(
+                w,
+                "Задача не сбалансирована: добавлен фиктивный потребитель {}\n",
+                problem.consumer_label(self.demands.len())
+            )?;
+        }
+
+        writeln!(w, "=== НАЧАЛЬНЫЙ ПЛАН (метод северо-западного угла) ===")?;
+        let mut plan = problem.north_west_corner();
+        problem.write_plan(w, &plan)?;
+
+        let missing = problem.degeneracy(&plan);
+        if missing > 0 {
+            writeln!(
+                w,
+                "\nВнимание: план вырожденный, не хватает базисных клеток: {} \
+                 (будут добавлены эпсилон-клетки)",
+                missing
+            )?;
+        }
+
+        writeln!(w, "\n=== ОПТИМИЗАЦИЯ МЕТОДОМ ПОТЕНЦИАЛОВ ===")?;
+        plan = problem.optimize(plan, w, PivotRule::MostNegative);
+
+        writeln!(w, "\n=== ОПТИМАЛЬНЫЙ ПЛАН ===")?;
+        problem.write_plan(w, &plan)?;
+        writeln!(
+            w,
+            "Без ограничений по запасам: {} {} (ограничения добавляют {} {})",
+            self.resolved_number_format()
+                .format(self.unconstrained_minimum()),
+            self.unit,
+            self.resolved_number_format()
+                .format(plan.total_cost - self.unconstrained_minimum()),
+            self.unit
+        )?;
+
+        let (suppliers, consumers) = self.binding_constraints(&plan);
+        let labels = |prefix: &str, indices: &[usize]| {
+            indices
+                .iter()
+                .map(|k| format!("{}{}", prefix, k + 1))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        writeln!(w, "Исчерпанные запасы: {}", labels("A", &suppliers))?;
+        writeln!(
+            w,
+            "Удовлетворенные потребности: {}",
+            labels("B", &consumers)
+        )?;
+        let slack = |prefix: &str, remaining: Vec<(usize, i32)>| {
+            remaining
+                .iter()
+                .filter(|&&(_, amount)| amount > 0)
+                .map(|&(k, amount)| format!("{}{} ({})", prefix, k + 1, amount))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let unused = slack(
+            "A",
+            (0..m)
+                .map(|i| {
+                    (
+                        i,
+                        self.supplies[i] - plan.allocations[i][..n].iter().sum::<i32>(),
+                    )
+                })
+                .collect(),
+        );
+        if !unused.is_empty() {
+            writeln!(w, "Остаток запасов: {}", unused)?;
+        }
+        let unmet = slack(
+            "B",
+            (0..n)
+                .map(|j| {
+                    (
+                        j,
+                        self.demands[j] - (0..m).map(|i| plan.allocations[i][j]).sum::<i32>(),
+                    )
+                })
+                .collect(),
+        );
+        if !unmet.is_empty() {
+            writeln!(w, "Недопоставка: {}", unmet)?;
+        }
+        writeln!(
+            w,
+            "Неравномерность загрузки поставщиков: {:.3}",
+            self.load_balance_index(&plan)
+        )?;
+        writeln!(
+            w,
+            "Уровень обслуживания потребителей: {:.1}%",
+            self.fill_rate(&plan) * 100.0
+        )?;
+
+        if self.route_capacities.is_some() {
+            writeln!(w, "\n=== ПЛАН С УЧЕТОМ ПРОПУСКНОЙ СПОСОБНОСТИ ===")?;
+            match self.solve_capacitated() {
+                Ok(plan) => {
+                    self.write_plan(w, &plan)?;
+                    let saturated: Vec<String> = self
+                        .saturated_routes(&plan)
+                        .into_iter()
+                        .map(|(i, j)| {
+                            format!("{} -> {}", self.supplier_label(i), self.consumer_label(j))
+                        })
+                        .collect();
+                    if !saturated.is_empty() {
+                        writeln!(
+                            w,
+                            "Примечание: маршруты загружены полностью: {}",
+                            saturated.join(", ")
+                        )?;
+                    }
+                }
+                Err(e) => writeln!(w, "{}", e)?,
+            }
+        }
+        Ok(())
+    }
+
+    // Пояснение решения по истории поворотов: нумерованные шаги в stdout
+    pub fn print_narrative(&self, history: &[IterationStep]) {
+        let _ = self.write_narrative(&mut io::stdout(), history);
+    }
+
+    pub fn write_narrative(&self, w: &mut dyn Write, history: &[IterationStep]) -> io::Result<()> {
+        for step in history {
+            let (i, j) = step.entering;
+            // Обход цикла по вершинам: клетки чередуют общий столбец и общую строку
+            let mut path = self.supplier_label(i);
+            for (k, &(ci, cj)) in step.cycle.iter().enumerate() {
+                path.push('→');
+                if k % 2 == 0 {
+                    path.push_str(&self.consumer_label(cj));
+                } else {
+                    path.push_str(&self.supplier_label(ci));
+                }
+            }
+
+            writeln!(
+                w,
+                "Шаг {}: маршрут {}→{} введен в базис, по циклу {} перераспределено {} ед., \
+                 стоимость снижена на {} до {} {}",
+                step.iteration,
+                self.supplier_label(i),
+                self.consumer_label(j),
+                path,
+                step.amount,
+                -cost_change(step.delta, step.amount),
+                step.cost,
+                self.unit
+            )?;
+        }
+        Ok(())
+    }
+
+    // Разбор одного поворота для занятий: потенциалы текущего базиса,
+    // оценка выбранной клетки, цикл со знаками, перемещаемый объем и
+    // изменение стоимости. План не меняется — так можно разобрать любую
+    // клетку-кандидата, а не только выбранную решателем
+    pub fn demonstrate_pivot(&self, plan: &TransportPlan, i: usize, j: usize) -> String {
+        let problem = self.balanced();
+        let m = problem.supplies.len();
+        let n = problem.demands.len();
+        let route = format!("{}→{}", self.supplier_label(i), self.consumer_label(j));
+        if i >= m || j >= n {
+            return format!("Клетка {} вне таблицы {}x{}\n", route, m, n);
+        }
+
+        let basis = problem.basis_of(&plan.allocations);
+        if basis[i][j] {
+            return format!("Клетка {} уже в базисе, ее оценка равна 0\n", route);
+        }
+        let Ok((u, v)) = problem.potentials_for_basis(&basis) else {
+            return "Базис не связан, потенциалы не определены\n".to_string();
+        };
+
+        let show = |values: &[f64]| {
+            values
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let delta = problem.reduced_cost(i, j, &u, &v);
+        let mut text = format!("Потенциалы: u = [{}], v = [{}]\n", show(&u), show(&v));
+        text.push_str(&format!(
+            "Оценка клетки {}: {} - ({}) - ({}) = {}\n",
+            route, problem.costs[i][j], u[i], v[j], delta
+        ));
+
+        let Some(cycle) = problem.find_cycle(&basis, i, j) else {
+            text.push_str("Цикл пересчета не найден\n");
+            return text;
+        };
+        let cells: Vec<String> = cycle
+            .iter()
+            .enumerate()
+            .map(|(k, &(ci, cj))| {
+                let sign = if k % 2 == 0 { '+' } else { '-' };
+                format!(
+                    "{}→{}({}{})",
+                    self.supplier_label(ci),
+                    self.consumer_label(cj),
+                    sign,
+                    plan.allocations[ci][cj]
+                )
+            })
+            .collect();
+        text.push_str(&format!("Цикл: {}\n", cells.join(" ")));
+
+        let amount = cycle
+            .iter()
+            .skip(1)
+            .step_by(2)
+            .map(|&(ci, cj)| plan.allocations[ci][cj])
+            .min()
+            .unwrap_or(0);
+        text.push_str(&format!(
+            "Перемещаемый объем: {} ед. (наименьший груз в клетках со знаком -)\n",
+            amount
+        ));
+
+        let change = cost_change(delta, amount);
+        let cost = problem.calculate_total_cost(&plan.allocations);
+        text.push_str(&format!(
+            "Изменение стоимости: {} · {} = {} ({} → {} {})\n",
+            delta,
+            amount,
+            change,
+            cost,
+            cost + change,
+            self.unit
+        ));
+        if delta > -EPSILON {
+            text.push_str("Оценка неотрицательна: поворот не улучшает план\n");
+        }
+        text
+    }
+
+    // Вывод плана в табличном виде
+    pub fn print_plan(&self, plan: &TransportPlan) {
+        let _ = self.write_plan(&mut io::stdout(), plan);
+    }
+
+    // Табличный вывод плана в произвольный приемник
+    pub fn write_plan(&self, w: &mut dyn Write, plan: &TransportPlan) -> io::Result<()> {
+        self.write_plan_styled(w, plan, TableStyle::default())
+    }
+
+    // Вывод плана в выбранном виде таблицы
+    pub fn write_plan_styled(
+        &self,
+        w: &mut dyn Write,
+        plan: &TransportPlan,
+        style: TableStyle,
+    ) -> io::Result<()> {
+        match style {
+            TableStyle::Combined => self.write_combined_table(w, plan),
+            TableStyle::Split => self.write_split_tables(w, plan),
+        }
+    }
+
+    // Две таблицы с выравниванием по самой широкой ячейке
+    fn write_split_tables(&self, w: &mut dyn Write, plan: &TransportPlan) -> io::Result<()> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let number_format = self.resolved_number_format();
+        let num = |value: i32| number_format.format(value);
+
+        let mut header = vec![String::new()];
+        header.extend((0..n).map(|j| self.consumer_label(j)));
+        header.push("Запасы".to_string());
+
+        let mut allocations = vec![header.clone()];
+        for i in 0..m {
+            let mut row = vec![self.supplier_label(i)];
+            row.extend((0..n).map(|j| match plan.allocations[i][j] {
+                0 => "-".to_string(),
+                allocation => num(allocation),
+            }));
+            row.push(format!(
+                "{}/{}",
+                num(plan.allocations.row_sum(i)),
+                num(self.supplies[i])
+            ));
+            allocations.push(row);
+        }
+        let mut demands = vec!["Потр.".to_string()];
+        demands.extend((0..n).map(|j| {
+            format!(
+                "{}/{}",
+                num(plan.allocations.col_sum(j)),
+                num(self.demands[j])
+            )
+        }));
+        demands.push(String::new());
+        allocations.push(demands);
+
+        header.pop();
+        let mut costs = vec![header];
+        for i in 0..m {
+            let mut row = vec![self.supplier_label(i)];
+            row.extend((0..n).map(|j| {
+                if self.is_forbidden(i, j) {
+                    "x".to_string()
+                } else {
+                    num(self.costs[i][j])
+                }
+            }));
+            costs.push(row);
+        }
+
+        writeln!(w, "Перевозки:")?;
+        write_aligned(w, &allocations)?;
+        writeln!(w, "\nСтоимости:")?;
+        write_aligned(w, &costs)?;
+        writeln!(
+            w,
+            "\nОбщая стоимость: {} {}",
+            num(plan.total_cost),
+            self.unit
+        )
+    }
+
+    fn write_combined_table(&self, w: &mut dyn Write, plan: &TransportPlan) -> io::Result<()> {
+        let m = self.supplies.len();
+        let n = self.demands.len();
+        let number_format = self.resolved_number_format();
+        let num = |value: i32| number_format.format(value);
+
+        // Заголовок таблицы
+        write!(w, "      ")?;
+        for j in 0..n {
+            write!(w, "{:.<6}", self.consumer_label(j))?;
+        }
+        writeln!(w, "| Запасы")?;
+
+        // Данные
+        for i in 0..m {
+            write!(w, "{}   ", self.supplier_label(i))?;
+            for j in 0..n {
+                if plan.allocations[i][j] > 0 {
+                    write!(
+                        w,
+                        "{}({})\t",
+                        num(plan.allocations[i][j]),
+                        num(self.costs[i][j])
+                    )?;
+                } else if self.is_forbidden(i, j) {
+                    write!(w, "x\t")?;
+                } else {
+                    write!(w, "-\t")?;
+                }
+            }
+            writeln!(
+                w,
+                "| {}/{}",
+                num(plan.allocations.row_sum(i)),
+                num(self.supplies[i])
+            )?;
+        }
+
+        // Потребности
+        write!(w, "Потр.")?;
+        for j in 0..n {
+            write!(
+                w,
+                " {}/{}  ",
+                num(plan.allocations.col_sum(j)),
+                num(self.demands[j])
+            )?;
+        }
+
+        writeln!(
+            w,
+            "\n\nОбщая стоимость: {} {}",
+            num(plan.total_cost),
+            self.unit
+        )
+    }
+}
+
+// Изменение стоимости при перемещении amount единиц по циклу с оценкой
+// delta: единственное место, где дробная оценка встречается с целыми
+// перевозками; результат округляется до целого
+pub(crate) fn cost_change(delta: f64, amount: i32) -> i32 {
+    (delta * amount as f64).round() as i32
+}
+
+// Деление total по долям с округлением вниз; остаток добавляется к
+// наибольшей доле (при равенстве — к первой). None для отрицательных,
+// нечисловых долей или нулевой суммы
+fn split_by_ratios(total: i32, ratios: &[f64]) -> Option<Vec<i32>> {
+    if ratios.iter().any(|r| !r.is_finite() || *r < 0.0) {
+        return None;
+    }
+    let sum: f64 = ratios.iter().sum();
+    if sum <= 0.0 {
+        return None;
+    }
+
+    let mut parts: Vec<i32> = ratios
+        .iter()
+        .map(|r| (total as f64 * r / sum).floor() as i32)
+        .collect();
+    let largest =
+        (0..ratios.len()).fold(0, |best, k| if ratios[k] > ratios[best] { k } else { best });
+    parts[largest] += total - parts.iter().sum::<i32>();
+    Some(parts)
+}
+
+// Пропорциональное масштабирование целых значений до суммы total методом
+// наибольших остатков: сумма результата равна total точно
+fn scale_proportionally(values: &[i32], total: i32) -> Vec<i32> {
+    let sum: i64 = values.iter().map(|&v| v as i64).sum();
+    if sum == 0 {
+        return values.to_vec();
+    }
+
+    let mut scaled: Vec<i32> = Vec::with_capacity(values.len());
+    let mut remainders: Vec<(i64, usize)> = Vec::with_capacity(values.len());
+    for (k, &value) in values.iter().enumerate() {
+        let exact = value as i64 * total as i64;
+        scaled.push((exact / sum) as i32);
+        remainders.push((exact % sum, k));
+    }
+
+    let missing = total - scaled.iter().sum::<i32>();
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, k) in remainders.iter().take(missing.max(0) as usize) {
+        scaled[k] += 1;
+    }
+    scaled
+}
+
+// Независимая обработка элементов в нескольких потоках: срез делится на
+// равные куски по числу ядер, порядок результатов совпадает с порядком входа
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    let f = &f;
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("рабочий поток завершился паникой"))
+            .collect()
+    })
+}
+
+// Таблица строк, выровненных по правому краю: ширина столбца — по самой
+// длинной ячейке, первый столбец (подписи строк) выравнивается влево
+fn write_aligned(w: &mut dyn Write, table: &[Vec<String>]) -> io::Result<()> {
+    let cols = table.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..cols)
+        .map(|c| {
+            table
+                .iter()
+                .filter_map(|row| row.get(c))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    for row in table {
+        let mut line = String::new();
+        for (c, cell) in row.iter().enumerate() {
+            if c == 0 {
+                line.push_str(&format!("{:<width$}", cell, width = widths[c]));
+            } else {
+                line.push_str(&format!("  {:>width$}", cell, width = widths[c]));
+            }
+        }
+        writeln!(w, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+impl Default for TransportProblem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransportPlan {
+    // Перевозки в виде дробных чисел (например, для внешних LP-решателей
+    // или round_to_integer)
+    pub fn to_f64(&self) -> Vec<Vec<f64>> {
+        self.allocations
+            .iter()
+            .map(|row| row.iter().map(|&x| x as f64).collect())
+            .collect()
+    }
+
+    // План из дробных перевозок: каждое значение округляется до ближайшего
+    // целого, половины — от нуля (f64::round); NaN становится 0, значения
+    // вне диапазона i32 насыщаются. Округление по клеткам может нарушить
+    // баланс — для согласованного округления есть round_to_integer.
+    // Стоимость считается по задаче problem; размеры должны совпадать с ней
+    // или с ее сбалансированным вариантом
+    pub fn from_f64(
+        allocations: &[Vec<f64>],
+        problem: &TransportProblem,
+    ) -> Result<TransportPlan, TransportError> {
+        let rounded: Vec<Vec<i32>> = allocations
+            .iter()
+            .map(|row| row.iter().map(|&x| x.round() as i32).collect())
+            .collect();
+        let total_cost = problem
+            .evaluate(&rounded)
+            .or_else(|_| problem.balanced().evaluate(&rounded))?;
+        Ok(TransportPlan {
+            allocations: Matrix::try_from(rounded)?,
+            total_cost,
+        })
+    }
+
+    // Пересчет общей стоимости после ручного изменения распределения
+    pub fn recompute_cost(&mut self, problem: &TransportProblem) {
+        self.total_cost = problem.calculate_total_cost(&self.allocations);
+    }
+
+    // Проверка, что сохраненная стоимость соответствует распределению
+    pub fn cost_is_consistent(&self, problem: &TransportProblem) -> bool {
+        self.total_cost == problem.calculate_total_cost(&self.allocations)
+    }
+
+    // Занятые клетки плана (i, j, объем) в построчном порядке
+    pub fn occupied_cells(&self) -> impl Iterator<Item = (usize, usize, i32)> + '_ {
+        self.allocations.iter().enumerate().flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &allocation)| allocation > 0)
+                .map(move |(j, &allocation)| (i, j, allocation))
+        })
+    }
+
+    // План как список перевозок по занятым клеткам
+    pub fn shipments(&self, problem: &TransportProblem) -> Vec<Shipment> {
+        self.occupied_cells()
+            .map(|(i, j, amount)| Shipment {
+                from: i,
+                to: j,
+                amount,
+                unit_cost: problem.costs[i][j],
+                total: amount * problem.costs[i][j],
+            })
+            .collect()
+    }
+
+    // Проверка отсутствия отрицательных перевозок: в отладочной сборке
+    // нарушение вызывает панику, в релизной возвращается список клеток
+    pub fn assert_nonnegative(&self) -> Result<(), TransportError> {
+        let mut cells = Vec::new();
+        for (i, row) in self.allocations.iter().enumerate() {
+            for (j, &allocation) in row.iter().enumerate() {
+                if allocation < 0 {
+                    cells.push((i, j));
+                }
+            }
+        }
+
+        debug_assert!(
+            cells.is_empty(),
+            "отрицательные перевозки в клетках {:?}",
+            cells
+        );
+
+        if cells.is_empty() {
+            Ok(())
+        } else {
+            Err(TransportError::NegativeAllocation { cells })
+        }
+    }
+}
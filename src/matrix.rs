@@ -0,0 +1,158 @@
+use std::ops::{Deref, Index, IndexMut};
+
+use crate::TransportError;
+
+// Прямоугольная матрица (стоимости, перевозки): размеры фиксируются при
+// создании, строки нельзя удлинить или укоротить через индексацию
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<Vec<i32>>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix {
+            rows,
+            cols,
+            data: vec![vec![0; cols]; rows],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> Option<i32> {
+        self.data.get(i).and_then(|row| row.get(j)).copied()
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, value: i32) -> Result<(), TransportError> {
+        if i >= self.rows {
+            return Err(TransportError::IndexOutOfRange {
+                index: i,
+                len: self.rows,
+            });
+        }
+        if j >= self.cols {
+            return Err(TransportError::IndexOutOfRange {
+                index: j,
+                len: self.cols,
+            });
+        }
+        self.data[i][j] = value;
+        Ok(())
+    }
+
+    pub fn row_sum(&self, i: usize) -> i32 {
+        self.data[i].iter().sum()
+    }
+
+    pub fn col_sum(&self, j: usize) -> i32 {
+        self.data.iter().map(|row| row[j]).sum()
+    }
+
+    // Добавление строки; длина должна совпадать с числом столбцов
+    pub fn push_row(&mut self, row: Vec<i32>) {
+        assert_eq!(row.len(), self.cols, "длина строки не совпадает с матрицей");
+        self.data.push(row);
+        self.rows += 1;
+    }
+
+    // Удаление строки с возвратом ее значений
+    pub fn remove_row(&mut self, i: usize) -> Vec<i32> {
+        assert!(
+            i < self.rows,
+            "индекс строки {} вне диапазона 0..{}",
+            i,
+            self.rows
+        );
+        let row = self.data.remove(i);
+        self.rows -= 1;
+        row
+    }
+
+    // Перестановка двух строк
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        assert!(
+            a < self.rows && b < self.rows,
+            "индексы строк {} и {} вне диапазона 0..{}",
+            a,
+            b,
+            self.rows
+        );
+        self.data.swap(a, b);
+    }
+
+    // Добавление столбца; длина должна совпадать с числом строк
+    pub fn push_col(&mut self, col: Vec<i32>) {
+        assert_eq!(
+            col.len(),
+            self.rows,
+            "длина столбца не совпадает с матрицей"
+        );
+        for (row, value) in self.data.iter_mut().zip(col) {
+            row.push(value);
+        }
+        self.cols += 1;
+    }
+}
+
+impl TryFrom<Vec<Vec<i32>>> for Matrix {
+    type Error = TransportError;
+
+    fn try_from(data: Vec<Vec<i32>>) -> Result<Self, Self::Error> {
+        let cols = data.first().map_or(0, |row| row.len());
+        for (i, row) in data.iter().enumerate() {
+            if row.len() != cols {
+                return Err(TransportError::RaggedRow {
+                    row: i,
+                    expected: cols,
+                    found: row.len(),
+                });
+            }
+        }
+        Ok(Matrix {
+            rows: data.len(),
+            cols,
+            data,
+        })
+    }
+}
+
+// Чтение как срез строк: итерация и передача в функции, принимающие &[Vec<i32>]
+impl Deref for Matrix {
+    type Target = [Vec<i32>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl Index<usize> for Matrix {
+    type Output = [i32];
+
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.data[i]
+    }
+}
+
+impl IndexMut<usize> for Matrix {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        &mut self.data[i]
+    }
+}
+
+impl<'a> IntoIterator for &'a Matrix {
+    type Item = &'a Vec<i32>;
+    type IntoIter = std::slice::Iter<'a, Vec<i32>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
@@ -0,0 +1,239 @@
+use transport_problem::{
+    FORBIDDEN, InitialBasis, Matrix, PlanIssue, TransportError, TransportProblem,
+};
+
+fn optimum(supplies: Vec<i32>, demands: Vec<i32>, costs: Vec<Vec<i32>>) -> i32 {
+    TransportProblem::try_new(supplies, demands, costs)
+        .unwrap()
+        .solve_to_plan()
+        .total_cost
+}
+
+#[test]
+fn built_in_problem() {
+    assert_eq!(TransportProblem::new().solve_to_plan().total_cost, 6520);
+}
+
+#[test]
+fn vogel_textbook_example() {
+    let cost = optimum(
+        vec![7, 9, 18],
+        vec![5, 8, 7, 14],
+        vec![
+            vec![19, 30, 50, 10],
+            vec![70, 30, 40, 60],
+            vec![40, 8, 70, 20],
+        ],
+    );
+    assert_eq!(cost, 743);
+}
+
+#[test]
+fn metro_water_district() {
+    // Hillier, Lieberman: фиктивный поставщик и запрещенные маршруты
+    let cost = optimum(
+        vec![50, 60, 50, 50],
+        vec![30, 20, 70, 30, 60],
+        vec![
+            vec![16, 16, 13, 22, 17],
+            vec![14, 14, 13, 19, 15],
+            vec![19, 19, 20, 23, FORBIDDEN],
+            vec![FORBIDDEN, 0, FORBIDDEN, 0, 0],
+        ],
+    );
+    assert_eq!(cost, 2460);
+}
+
+#[test]
+fn four_by_three() {
+    let cost = optimum(
+        vec![15, 25, 10],
+        vec![5, 15, 15, 15],
+        vec![vec![10, 2, 20, 11], vec![12, 7, 9, 20], vec![4, 14, 16, 18]],
+    );
+    assert_eq!(cost, 435);
+}
+
+#[test]
+fn unbalanced_supply_surplus() {
+    let cost = optimum(
+        vec![20, 30],
+        vec![10, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    );
+    assert_eq!(cost, 90);
+}
+
+#[test]
+fn degenerate_initial_plan() {
+    let cost = optimum(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 1]]);
+    assert_eq!(cost, 30);
+}
+
+#[test]
+fn assignment_problem() {
+    let cost = optimum(
+        vec![1, 1, 1],
+        vec![1, 1, 1],
+        vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]],
+    );
+    assert_eq!(cost, 5);
+}
+
+#[test]
+fn second_best_cost_is_one_pivot_away() {
+    assert_eq!(TransportProblem::new().second_best_cost(), Some(6580));
+
+    // Все маршруты стоят одинаково: любой поворот сохраняет стоимость
+    let flat =
+        TransportProblem::try_new(vec![5, 5], vec![5, 5], vec![vec![2, 2], vec![2, 2]]).unwrap();
+    assert_eq!(flat.second_best_cost(), None);
+}
+
+#[test]
+fn canonical_plan_snapshot() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_canonical();
+    assert_eq!(
+        plan.allocations,
+        Matrix::try_from(vec![
+            vec![90, 0, 0, 0, 110],
+            vec![0, 80, 70, 0, 0],
+            vec![0, 20, 0, 130, 0],
+        ])
+        .unwrap()
+    );
+    assert_eq!(plan.allocations, problem.solve_canonical().allocations);
+
+    // Первый поворот освобождает сразу A1 -> B1 и A2 -> B2; из базиса
+    // выводится A1 -> B1 с меньшим индексом
+    let tied = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![10, 10, 10],
+        vec![vec![3, 1, 2], vec![1, 3, 2], vec![2, 2, 1]],
+    )
+    .unwrap();
+    assert_eq!(
+        tied.solve_canonical().allocations,
+        Matrix::try_from(vec![vec![0, 10, 0], vec![10, 0, 0], vec![0, 0, 10]]).unwrap()
+    );
+}
+
+#[test]
+fn assume_balanced_matches_checked_solve() {
+    let problem = TransportProblem::new();
+    assert!(problem.is_balanced());
+
+    let fast = problem.solve_assume_balanced();
+    let checked = problem.solve_to_plan();
+    assert_eq!(fast.allocations, checked.allocations);
+    assert_eq!(fast.total_cost, checked.total_cost);
+}
+
+#[test]
+fn worst_plan_brackets_optimum() {
+    let problem = TransportProblem::new();
+    let worst = problem.worst_plan();
+
+    assert!(worst.total_cost >= problem.solve_to_plan().total_cost);
+    assert_eq!(worst.total_cost, 9020);
+    assert!(
+        problem
+            .validate_plan(&worst)
+            .iter()
+            .all(|issue| matches!(issue, PlanIssue::Degenerate { .. }))
+    );
+}
+
+#[test]
+fn dual_objective_matches_primal_optimum() {
+    let problems = [
+        TransportProblem::new(),
+        TransportProblem::try_new(
+            vec![20, 30],
+            vec![10, 15, 10],
+            vec![vec![2, 3, 1], vec![5, 4, 8]],
+        )
+        .unwrap(),
+        TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 4]])
+            .unwrap(),
+    ];
+
+    for problem in problems {
+        let (u, v, objective) = problem.solve_dual().unwrap();
+        assert!((objective - problem.solve_to_plan().total_cost as f64).abs() < 1e-9);
+
+        // Двойственная допустимость: u_i + v_j <= c_ij
+        let balanced = problem.balanced();
+        for i in 0..u.len() {
+            for j in 0..v.len() {
+                assert!(u[i] + v[j] <= balanced.costs[i][j] as f64 + 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn unconstrained_minimum_bounds_optimum() {
+    let problem = TransportProblem::new();
+    // 90·12 + 100·8 + 70·15 + 130·11 + 110·17
+    assert_eq!(problem.unconstrained_minimum(), 6230);
+    assert!(problem.unconstrained_minimum() <= problem.solve_to_plan().total_cost);
+}
+
+#[test]
+fn full_solution_has_matching_primal_and_dual() {
+    let problem = TransportProblem::new();
+    let solution = problem.solve_full().unwrap();
+
+    assert!(solution.converged);
+    assert_eq!(solution.plan.total_cost, 6520);
+    assert!((solution.dual_objective - solution.plan.total_cost as f64).abs() < 1e-9);
+
+    // Любой допустимый план здесь стоит 90
+    let flat = TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 4]])
+        .unwrap();
+    let solution = flat.solve_full().unwrap();
+    assert_eq!(solution.plan.total_cost, 90);
+    assert!(solution.alternative_optima);
+
+    let blocked =
+        TransportProblem::try_new(vec![10, 10], vec![20], vec![vec![1], vec![FORBIDDEN]]).unwrap();
+    assert!(blocked.solve_full().is_err());
+}
+
+#[test]
+fn robust_solve_handles_degenerate_problem() {
+    // Северо-западный угол дает вырожденный план из двух клеток
+    let problem =
+        TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 1]])
+            .unwrap();
+    let plan = problem.robust_solve().unwrap();
+
+    assert_eq!(plan.total_cost, 30);
+    assert_eq!(plan, problem.solve_to_plan());
+}
+
+#[test]
+fn robust_solve_retries_after_stall() {
+    let problem = TransportProblem::new();
+    let mut attempts = Vec::new();
+    let plan = problem
+        .robust_solve_with_callback(|method, _| {
+            if attempts.last() != Some(&method) {
+                attempts.push(method);
+            }
+            method != InitialBasis::NorthWestCorner
+        })
+        .unwrap();
+
+    assert_eq!(plan.total_cost, 6520);
+    assert_eq!(attempts[0], InitialBasis::NorthWestCorner);
+    assert!(attempts.len() > 1);
+
+    // Все попытки прерываются на первом повороте
+    assert_eq!(
+        problem.robust_solve_with_callback(|_, _| false),
+        Err(TransportError::NotConverged)
+    );
+}
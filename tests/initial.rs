@@ -0,0 +1,231 @@
+use std::time::Instant;
+
+use transport_problem::{InitialBasis, Matrix, PivotRule, TransportError, TransportProblem};
+
+#[test]
+fn pivot_rules_reach_same_optimum() {
+    let problem = TransportProblem::new();
+    let iterations: Vec<usize> = [
+        PivotRule::MostNegative,
+        PivotRule::FirstNegative,
+        PivotRule::LargestImprovement,
+    ]
+    .into_iter()
+    .map(|rule| {
+        let result = problem.optimize_with_rule(problem.north_west_corner(), rule);
+        assert!(result.converged);
+        assert_eq!(result.plan.total_cost, 6520);
+        result.iterations
+    })
+    .collect();
+
+    // Первая же отрицательная оценка ведет к оптимуму дольше
+    assert_eq!(iterations, vec![3, 5, 3]);
+    assert_eq!(
+        iterations[0],
+        problem.pivots_to_optimum(InitialBasis::NorthWestCorner)
+    );
+}
+
+#[test]
+fn callback_fires_once_per_pivot_and_can_cancel() {
+    let problem = TransportProblem::new();
+    let mut seen = Vec::new();
+    let result = problem.optimize_with_callback(problem.north_west_corner(), |progress| {
+        seen.push((progress.iterations, progress.plan.total_cost));
+        true
+    });
+
+    assert!(result.converged);
+    assert_eq!(seen.len(), result.iterations);
+    assert!(
+        seen.iter()
+            .enumerate()
+            .all(|(k, &(iteration, _))| iteration == k + 1)
+    );
+    assert_eq!(seen.last().unwrap().1, result.plan.total_cost);
+
+    // Отмена после первого поворота
+    let mut calls = 0;
+    let cancelled = problem.optimize_with_callback(problem.north_west_corner(), |_| {
+        calls += 1;
+        false
+    });
+    assert_eq!(calls, 1);
+    assert_eq!(cancelled.iterations, 1);
+    assert!(!cancelled.converged);
+    assert!(cancelled.plan.total_cost > result.plan.total_cost);
+}
+
+// Замер на большой задаче: cargo test --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn large_instance_timing() {
+    let n: usize = 200;
+    let mut seed: u64 = 1;
+    let mut next_cost = || {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as i32 % 100 + 1
+    };
+    let costs: Vec<Vec<i32>> = (0..n)
+        .map(|_| (0..n).map(|_| next_cost()).collect())
+        .collect();
+    let supplies: Vec<i32> = (0..n).map(|i| 50 + (i * 17 % 31) as i32).collect();
+    let mut demands: Vec<i32> = (0..n).map(|j| 50 + (j * 23 % 29) as i32).collect();
+    demands[0] += supplies.iter().sum::<i32>() - demands.iter().sum::<i32>();
+    let problem = TransportProblem::try_new(supplies, demands, costs).unwrap();
+
+    // От северо-западного угла поворотов больше, чем MAX_ITERATIONS
+    let start = Instant::now();
+    let result = problem.optimize_with_rule(problem.least_cost(), PivotRule::MostNegative);
+    let elapsed = start.elapsed();
+
+    assert!(result.converged);
+    println!(
+        "{}x{}: {} поворотов за {:?}, стоимость {}",
+        n, n, result.iterations, elapsed, result.plan.total_cost
+    );
+}
+
+#[test]
+fn dry_run_returns_initial_plan_cost() {
+    let problem = TransportProblem::new();
+
+    assert_eq!(
+        problem
+            .solve_no_optimize(InitialBasis::NorthWestCorner)
+            .total_cost,
+        problem.north_west_corner().total_cost
+    );
+    assert_eq!(
+        problem
+            .solve_no_optimize(InitialBasis::LeastCost)
+            .total_cost,
+        7080
+    );
+    assert_eq!(
+        problem.solve_no_optimize(InitialBasis::Vogel).total_cost,
+        6540
+    );
+}
+
+#[test]
+fn initial_plans_are_feasible() {
+    let problem = TransportProblem::new();
+    let optimum = problem.solve_to_plan().total_cost;
+
+    for method in [
+        InitialBasis::NorthWestCorner,
+        InitialBasis::LeastCost,
+        InitialBasis::Vogel,
+        InitialBasis::Russell,
+    ] {
+        let plan = problem.solve_no_optimize(method);
+        for i in 0..problem.supplies.len() {
+            assert_eq!(plan.allocations.row_sum(i), problem.supplies[i]);
+        }
+        for j in 0..problem.demands.len() {
+            assert_eq!(plan.allocations.col_sum(j), problem.demands[j]);
+        }
+        assert!(plan.total_cost >= optimum);
+    }
+}
+
+#[test]
+fn plan_from_basis_back_solves_allocations() {
+    let problem = TransportProblem::new();
+    let nw = problem.north_west_corner();
+    let basis: Vec<(usize, usize)> = nw.occupied_cells().map(|(i, j, _)| (i, j)).collect();
+
+    assert_eq!(problem.plan_from_basis(&basis), Ok(nw));
+}
+
+#[test]
+fn plan_from_basis_rejects_cycles_and_negative_amounts() {
+    let problem = TransportProblem::new();
+
+    // (0,0)-(0,1)-(1,1)-(1,0) замыкаются в цикл, строка A3 не связана
+    let cyclic = [(0, 0), (0, 1), (1, 1), (1, 0), (0, 2), (0, 3), (0, 4)];
+    assert_eq!(
+        problem.plan_from_basis(&cyclic),
+        Err(TransportError::BasisNotTree)
+    );
+    assert!(matches!(
+        problem.plan_from_basis(&cyclic[..3]),
+        Err(TransportError::DimensionMismatch {
+            expected: 7,
+            found: 3
+        })
+    ));
+
+    // Дерево, но A2 и A3 целиком везут в B1 больше его потребности
+    let negative = [(0, 0), (1, 0), (2, 0), (0, 1), (0, 2), (0, 3), (0, 4)];
+    assert!(matches!(
+        problem.plan_from_basis(&negative),
+        Err(TransportError::NegativeAllocation { .. })
+    ));
+}
+
+#[test]
+fn vogel_needs_no_more_pivots_than_north_west() {
+    let problem = TransportProblem::new();
+    let north_west = problem.pivots_to_optimum(InitialBasis::NorthWestCorner);
+    let vogel = problem.pivots_to_optimum(InitialBasis::Vogel);
+
+    assert!(north_west > 0);
+    assert!(vogel <= north_west);
+}
+
+#[test]
+fn initial_cost_spread_covers_all_methods() {
+    let problem = TransportProblem::new();
+
+    // Рассел сразу дает оптимум 6520, северо-западный угол — 7360
+    assert_eq!(problem.initial_cost_spread(), (6520, 7360, 840));
+    assert_eq!(
+        problem.solve_no_optimize(InitialBasis::Russell).total_cost,
+        problem.solve_to_plan().total_cost
+    );
+}
+
+#[test]
+fn ordered_north_west_corner_follows_priority() {
+    let problem = TransportProblem::new();
+    let natural = problem.north_west_corner_ordered(&[0, 1, 2], &[0, 1, 2, 3, 4]);
+    assert_eq!(natural, problem.north_west_corner());
+
+    // Полный разворот обоих порядков дает ту же «лестницу», что и обычный обход
+    let reversed = problem.north_west_corner_ordered(&[2, 1, 0], &[4, 3, 2, 1, 0]);
+    assert_eq!(reversed, natural);
+
+    // A2 отгружает первым: B1 и часть B2 уходят от него
+    let plan = problem.north_west_corner_ordered(&[1, 0, 2], &[0, 1, 2, 3, 4]);
+    assert_eq!(
+        plan.allocations,
+        Matrix::try_from(vec![
+            vec![0, 40, 70, 90, 0],
+            vec![90, 60, 0, 0, 0],
+            vec![0, 0, 0, 40, 110],
+        ])
+        .unwrap()
+    );
+    for i in 0..problem.supplies.len() {
+        assert_eq!(plan.allocations.row_sum(i), problem.supplies[i]);
+    }
+    for j in 0..problem.demands.len() {
+        assert_eq!(plan.allocations.col_sum(j), problem.demands[j]);
+    }
+    assert_eq!(
+        plan.total_cost,
+        problem.calculate_total_cost(&plan.allocations)
+    );
+}
+
+#[test]
+#[should_panic(expected = "перестановкой")]
+fn ordered_north_west_corner_rejects_repeated_index() {
+    let problem = TransportProblem::new();
+    problem.north_west_corner_ordered(&[0, 0, 2], &[0, 1, 2, 3, 4]);
+}
@@ -0,0 +1,83 @@
+use transport_problem::{Matrix, TransportError};
+
+#[test]
+fn get_and_set_check_bounds() {
+    let mut matrix = Matrix::zeros(2, 3);
+    assert_eq!(matrix.rows(), 2);
+    assert_eq!(matrix.cols(), 3);
+
+    assert_eq!(matrix.set(1, 2, 7), Ok(()));
+    assert_eq!(matrix.get(1, 2), Some(7));
+    assert_eq!(matrix[1][2], 7);
+
+    assert_eq!(matrix.get(2, 0), None);
+    assert_eq!(matrix.get(0, 3), None);
+    assert_eq!(
+        matrix.set(2, 0, 1),
+        Err(TransportError::IndexOutOfRange { index: 2, len: 2 })
+    );
+    assert_eq!(
+        matrix.set(0, 3, 1),
+        Err(TransportError::IndexOutOfRange { index: 3, len: 3 })
+    );
+}
+
+#[test]
+fn row_and_column_sums() {
+    let matrix = Matrix::try_from(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+
+    assert_eq!(matrix.row_sum(0), 6);
+    assert_eq!(matrix.row_sum(1), 15);
+    assert_eq!(matrix.col_sum(0), 5);
+    assert_eq!(matrix.col_sum(2), 9);
+}
+
+#[test]
+fn push_row_and_column_grow_dimensions() {
+    let mut matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+    matrix.push_row(vec![5, 6]);
+    matrix.push_col(vec![7, 8, 9]);
+
+    assert_eq!((matrix.rows(), matrix.cols()), (3, 3));
+    assert_eq!(
+        matrix,
+        Matrix::try_from(vec![vec![1, 2, 7], vec![3, 4, 8], vec![5, 6, 9]]).unwrap()
+    );
+
+    assert_eq!(matrix.remove_row(0), vec![1, 2, 7]);
+    assert_eq!(matrix.rows(), 2);
+}
+
+#[test]
+#[should_panic(expected = "длина строки")]
+fn push_row_rejects_wrong_length() {
+    let mut matrix = Matrix::zeros(2, 2);
+    matrix.push_row(vec![1, 2, 3]);
+}
+
+#[test]
+fn ragged_input_is_rejected() {
+    assert_eq!(
+        Matrix::try_from(vec![vec![1, 2], vec![3]]),
+        Err(TransportError::RaggedRow {
+            row: 1,
+            expected: 2,
+            found: 1
+        })
+    );
+    assert_eq!(Matrix::try_from(Vec::new()).map(|m| m.rows()), Ok(0));
+}
+
+#[test]
+#[should_panic(expected = "индекс строки")]
+fn remove_row_from_empty_matrix_panics() {
+    let mut matrix = Matrix::zeros(0, 2);
+    matrix.remove_row(0);
+}
+
+#[test]
+#[should_panic(expected = "индексы строк")]
+fn swap_rows_checks_bounds() {
+    let mut matrix = Matrix::zeros(2, 2);
+    matrix.swap_rows(0, 2);
+}
@@ -0,0 +1,463 @@
+use transport_problem::{
+    FORBIDDEN, Matrix, NumberFormat, TableStyle, TransportError, TransportPlan, TransportProblem,
+};
+
+#[test]
+fn plan_display_renders_allocations_and_cost() {
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![vec![5, 0], vec![0, 7]]).unwrap(),
+        total_cost: 31,
+    };
+
+    assert_eq!(
+        plan.to_string(),
+        "    \tB1\tB2\nA1  \t5\t-\nA2  \t-\t7\nОбщая стоимость: 31 у.е."
+    );
+}
+
+#[test]
+fn custom_unit_appears_in_output() {
+    let problem = TransportProblem::new().with_unit("руб.");
+    let mut out = Vec::new();
+    problem.solve_to_writer(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+
+    assert!(text.contains("6520 руб."));
+    assert!(!text.contains("у.е."));
+}
+
+#[test]
+fn dot_has_one_edge_per_nonzero_route() {
+    let problem = TransportProblem::new();
+    let allocations = Matrix::try_from(vec![
+        vec![90, 0, 0, 0, 110],
+        vec![0, 80, 70, 0, 0],
+        vec![0, 20, 0, 130, 0],
+    ])
+    .unwrap();
+    let plan = TransportPlan {
+        total_cost: problem.calculate_total_cost(&allocations),
+        allocations,
+    };
+    let dot = problem.plan_to_dot(&plan);
+
+    let nonzero = plan
+        .allocations
+        .iter()
+        .flatten()
+        .filter(|&&x| x > 0)
+        .count();
+    assert_eq!(dot.matches(" -> ").count(), nonzero);
+    assert!(dot.contains("A3 -> B4 [label=\"130 (12)\", penwidth=5.0];"));
+    assert!(dot.starts_with("digraph transport {"));
+}
+
+#[test]
+fn csv_round_trip_reproduces_problem() {
+    let problems = [
+        TransportProblem::new(),
+        TransportProblem::try_new(
+            vec![20, 30],
+            vec![10, 15, 10],
+            vec![vec![2, 3, 1], vec![5, FORBIDDEN, 8]],
+        )
+        .unwrap(),
+    ];
+
+    for problem in problems {
+        let csv = problem.to_csv();
+        assert_eq!(TransportProblem::from_csv(&csv), Ok(problem));
+    }
+}
+
+#[test]
+fn directory_batch_survives_bad_file() {
+    let dir = std::env::temp_dir().join(format!("transport_batch_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.csv"), TransportProblem::new().to_csv()).unwrap();
+    std::fs::write(dir.join("b.csv"), ",10,20\n30,1,x\n").unwrap();
+    std::fs::write(dir.join("notes.txt"), "не задача").unwrap();
+
+    let results = TransportProblem::solve_directory(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, "a.csv");
+    assert_eq!(results[0].1.as_ref().unwrap().total_cost, 6520);
+    assert_eq!(results[1].0, "b.csv");
+    assert!(matches!(
+        results[1].1,
+        Err(TransportError::Parse { line: 2, .. })
+    ));
+}
+
+#[test]
+fn solve_writes_report_to_buffer() {
+    let problem = TransportProblem::new();
+    let mut out = Vec::new();
+    problem.solve_to_writer(&mut out).unwrap();
+
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("=== ОПТИМАЛЬНЫЙ ПЛАН ==="));
+    assert!(text.contains("план оптимален"));
+    assert!(text.contains("Общая стоимость: 6520 у.е."));
+}
+
+fn report(problem: &TransportProblem) -> String {
+    let mut out = Vec::new();
+    problem.solve_to_writer(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn surplus_problem_reports_non_binding_supplier() {
+    let problem = TransportProblem::try_new(
+        vec![20, 30],
+        vec![10, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+    let text = report(&problem);
+
+    assert!(text.contains("добавлен фиктивный потребитель B4"));
+    assert!(text.contains("Общая стоимость: 90 у.е."));
+    assert!(text.contains("Исчерпанные запасы: A1\n"));
+    assert!(text.contains("Остаток запасов: A2 (15)"));
+}
+
+#[test]
+fn split_table_aligns_allocations_and_costs() {
+    let problem =
+        TransportProblem::try_new(vec![5, 120], vec![100, 25], vec![vec![3, 1], vec![2, 10]])
+            .unwrap();
+    let plan = problem.solve_to_plan();
+    let mut out = Vec::new();
+    problem
+        .write_plan_styled(&mut out, &plan, TableStyle::Split)
+        .unwrap();
+
+    let text = String::from_utf8(out).unwrap();
+    let expected = "\
+Перевозки:
+            B1     B2   Запасы
+A1           -      5      5/5
+A2         100     20  120/120
+Потр.  100/100  25/25
+
+Стоимости:
+    B1  B2
+A1   3   1
+A2   2  10
+
+Общая стоимость: 405 у.е.
+";
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn summary_line_for_built_in_problem() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(
+        problem.summary_line(&plan),
+        "3x5 balanced | optimum=6520 | routes=6 | iters=3"
+    );
+}
+
+#[test]
+fn names_replace_default_labels() {
+    let problem = TransportProblem::try_new(vec![10, 5], vec![15], vec![vec![1], vec![2]])
+        .unwrap()
+        .with_names(
+            vec!["Склад Восток".to_string(), "Склад Запад".to_string()],
+            vec!["Магазин 12".to_string()],
+        )
+        .unwrap();
+    let plan = problem.solve_to_plan();
+
+    let mut out = Vec::new();
+    problem.write_plan(&mut out, &plan).unwrap();
+    let table = String::from_utf8(out).unwrap();
+    assert!(table.contains("Магазин 12"));
+    assert!(table.contains("Склад Запад   "));
+    assert!(!table.contains("A1"));
+
+    let dot = problem.plan_to_dot(&plan);
+    assert!(dot.contains("label=\"Склад Восток\\n10\""));
+    assert!(dot.contains("label=\"Магазин 12\\n15\""));
+}
+
+#[test]
+fn name_count_must_match() {
+    let problem = TransportProblem::new();
+    assert_eq!(
+        problem.with_names(vec!["Склад".to_string()], vec![]),
+        Err(TransportError::DimensionMismatch {
+            expected: 3,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn html_has_one_cell_per_route_and_total_cost() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    let html = problem.to_html(&plan);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert_eq!(html.matches("class=\"cell").count(), 3 * 5);
+    assert_eq!(
+        html.matches("class=\"cell used\"").count(),
+        plan.occupied_cells().count()
+    );
+    assert!(html.contains("Общая стоимость: 6520 у.е."));
+}
+
+#[test]
+fn degenerate_start_is_reported() {
+    // Первый поставщик закрывает первого потребителя целиком: у плана
+    // северо-западного угла две занятые клетки вместо трех
+    let problem =
+        TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 4]])
+            .unwrap();
+    let start = problem.north_west_corner();
+    assert_eq!(problem.degeneracy(&start), 1);
+
+    let mut out = Vec::new();
+    problem.solve_to_writer(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("не хватает базисных клеток: 1"));
+}
+
+#[test]
+fn print_every_thins_out_iteration_log() {
+    // Дешевая антидиагональ: план северо-западного угла далек от оптимума
+    let n: i32 = 8;
+    let costs = (0..n)
+        .map(|i| (0..n).map(|j| (i + j - (n - 1)).abs() * 3 + 1).collect())
+        .collect();
+    let problem = TransportProblem::try_new(vec![10; 8], vec![10; 8], costs)
+        .unwrap()
+        .with_print_every(5);
+    let iterations = problem
+        .optimize_with_callback(problem.north_west_corner(), |_| true)
+        .iterations;
+    assert!(iterations >= 10);
+
+    let mut out = Vec::new();
+    problem.solve_to_writer(&mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let logged: Vec<&str> = text
+        .lines()
+        .filter(|line| line.starts_with("Итерация"))
+        .collect();
+
+    assert_eq!(logged.len(), iterations / 5 + 1);
+    for (k, line) in logged.iter().take(iterations / 5).enumerate() {
+        assert!(line.starts_with(&format!("Итерация {}: улучшение", (k + 1) * 5)));
+    }
+    assert!(logged.last().unwrap().ends_with("план оптимален"));
+}
+
+#[test]
+fn narrative_describes_each_pivot() {
+    let problem = TransportProblem::new();
+    let (result, history) = problem.optimize_with_history(problem.north_west_corner());
+    assert_eq!(history.len(), result.iterations);
+    assert_eq!(history.last().unwrap().cost, 6520);
+
+    let mut out = Vec::new();
+    problem.write_narrative(&mut out, &history).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert_eq!(lines.len(), history.len());
+    assert_eq!(
+        lines[0],
+        "Шаг 1: маршрут A1→B5 введен в базис, по циклу A1→B5→A3→B4→A2→B3→A1 \
+         перераспределено 10 ед., стоимость снижена на 80 до 7280 у.е."
+    );
+    assert!(lines.last().unwrap().ends_with("до 6520 у.е."));
+}
+
+#[test]
+fn lp_format_has_term_per_route() {
+    let problem = TransportProblem::new();
+    let lp = problem.to_lp_format();
+
+    let objective = &lp[lp.find("Minimize").unwrap()..lp.find("Subject To").unwrap()];
+    assert_eq!(objective.matches("x_").count(), 3 * 5);
+    assert!(objective.contains(" obj: 12 x_1_1 + 15 x_1_2"));
+    assert!(lp.contains(" supply_1: x_1_1 + x_1_2 + x_1_3 + x_1_4 + x_1_5 = 200\n"));
+    assert!(lp.contains(" demand_5: x_1_5 + x_2_5 + x_3_5 = 110\n"));
+    assert!(lp.ends_with("End\n"));
+
+    let open = TransportProblem::try_new(vec![30, 20], vec![25], vec![vec![1], vec![2]]).unwrap();
+    let lp = open.to_lp_format();
+    assert!(lp.contains(" supply_1: x_1_1 <= 30\n"));
+    assert!(lp.contains(" demand_1: x_1_1 + x_2_1 >= 25\n"));
+}
+
+#[test]
+fn number_format_groups_digits() {
+    assert_eq!(NumberFormat::Plain.format(1200), "1200");
+    assert_eq!(NumberFormat::Spaces.format(1200), "1 200");
+    assert_eq!(NumberFormat::Commas.format(1234567), "1,234,567");
+    assert_eq!(NumberFormat::Commas.format(-1234567), "-1,234,567");
+    assert_eq!(NumberFormat::Spaces.format(999), "999");
+    assert_eq!(NumberFormat::Spaces.format(0), "0");
+    assert_eq!(NumberFormat::Commas.format(i32::MIN), "-2,147,483,648");
+
+    let problem = TransportProblem::new().with_number_format(NumberFormat::Spaces);
+    let mut out = Vec::new();
+    problem
+        .write_plan(&mut out, &problem.solve_to_plan())
+        .unwrap();
+    assert!(
+        String::from_utf8(out)
+            .unwrap()
+            .contains("Общая стоимость: 6 520 у.е.")
+    );
+}
+
+#[test]
+fn cost_trajectory_is_non_increasing() {
+    let problem = TransportProblem::new();
+    let start = problem.north_west_corner();
+    let (_, history) = problem.optimize_with_history(start.clone());
+    let csv = problem.cost_trajectory_csv(&history);
+
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("iteration,total_cost"));
+    let costs: Vec<i32> = lines
+        .enumerate()
+        .map(|(k, line)| {
+            let (iteration, cost) = line.split_once(',').unwrap();
+            assert_eq!(iteration.parse::<usize>().unwrap(), k);
+            cost.parse().unwrap()
+        })
+        .collect();
+
+    assert_eq!(costs.len(), history.len() + 1);
+    assert_eq!(costs[0], start.total_cost);
+    assert_eq!(*costs.last().unwrap(), 6520);
+    assert!(costs.windows(2).all(|pair| pair[1] <= pair[0]));
+}
+
+#[test]
+fn load_balance_is_reported_for_unbalanced_problem() {
+    let problem = TransportProblem::try_new(
+        vec![20, 30],
+        vec![10, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+
+    assert!(report(&problem).contains("Неравномерность загрузки поставщиков: 0.143"));
+}
+
+#[test]
+fn cost_magnitude_drives_auto_format() {
+    let problem = TransportProblem::new();
+    assert_eq!(problem.cost_magnitude(), (8, 26));
+    assert_eq!(
+        problem
+            .clone()
+            .with_number_format(NumberFormat::Auto)
+            .resolved_number_format(),
+        NumberFormat::Plain
+    );
+
+    let expensive = TransportProblem::try_new(
+        vec![100, 100],
+        vec![200],
+        vec![vec![12_500], vec![FORBIDDEN]],
+    )
+    .unwrap()
+    .with_number_format(NumberFormat::Auto);
+    assert_eq!(expensive.cost_magnitude(), (12_500, 12_500));
+    assert_eq!(expensive.resolved_number_format(), NumberFormat::Spaces);
+
+    let mut out = Vec::new();
+    expensive
+        .write_plan(&mut out, &expensive.north_west_corner())
+        .unwrap();
+    assert!(String::from_utf8(out).unwrap().contains("100(12 500)"));
+}
+
+#[test]
+fn demonstrated_pivot_matches_first_step() {
+    let problem = TransportProblem::new();
+    let start = problem.north_west_corner();
+    let text = problem.demonstrate_pivot(&start, 0, 4);
+
+    assert!(text.contains("Потенциалы: u = [0, -6, -5], v = [12, 15, 21, 17, 25]"));
+    assert!(text.contains("Оценка клетки A1→B5: 17 - (0) - (25) = -8"));
+    assert!(
+        text.contains("Цикл: A1→B5(+0) A3→B5(-110) A3→B4(+40) A2→B4(-90) A2→B3(+60) A1→B3(-10)")
+    );
+    assert!(text.contains("Перемещаемый объем: 10 ед."));
+    assert!(text.contains("= -80 (7360 → 7280 у.е.)"));
+
+    // Разбор не меняет план
+    assert_eq!(start, problem.north_west_corner());
+    assert!(
+        problem
+            .demonstrate_pivot(&start, 0, 0)
+            .contains("уже в базисе")
+    );
+}
+
+#[test]
+fn supply_shortage_lowers_fill_rate() {
+    let problem = TransportProblem::try_new(
+        vec![10, 20],
+        vec![15, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+    let text = report(&problem);
+
+    assert!(text.contains("добавлен фиктивный поставщик A3"));
+    assert!(text.contains("Недопоставка: B1 (10)"));
+    assert!(text.contains("Уровень обслуживания потребителей: 75.0%"));
+}
+
+#[test]
+fn history_frames_include_initial_state() {
+    let problem = TransportProblem::new();
+    let (result, history) = problem.optimize_with_history(problem.north_west_corner());
+    let frames = problem.history_to_frames(&history);
+
+    assert_eq!(frames.len(), history.len() + 1);
+    assert_eq!(frames.len(), result.iterations + 1);
+
+    let render = |plan: &TransportPlan| {
+        let mut buffer = Vec::new();
+        problem.write_plan(&mut buffer, plan).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+    assert_eq!(frames[0], render(&problem.north_west_corner()));
+    assert_eq!(frames.last().unwrap(), &render(&result.plan));
+    assert_eq!(problem.history_to_frames(&[]).len(), 1);
+}
+
+#[test]
+fn history_frames_show_dummy_consumer() {
+    let problem = TransportProblem::try_new(
+        vec![20, 30],
+        vec![10, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+    let balanced = problem.balanced();
+    let (result, history) = balanced.optimize_with_history(balanced.north_west_corner());
+    let frames = problem.history_to_frames(&history);
+
+    let mut buffer = Vec::new();
+    balanced.write_plan(&mut buffer, &result.plan).unwrap();
+    assert_eq!(frames.last().unwrap(), &String::from_utf8(buffer).unwrap());
+    assert!(frames.iter().all(|frame| frame.contains("B4")));
+}
@@ -0,0 +1,478 @@
+use transport_problem::{
+    Matrix, PivotRule, PlanIssue, PotentialError, TransportError, TransportPlan, TransportProblem,
+};
+
+// Оптимум встроенной задачи (6520), собранный вручную
+fn built_in_optimum(problem: &TransportProblem) -> TransportPlan {
+    let allocations = Matrix::try_from(vec![
+        vec![90, 0, 0, 0, 110],
+        vec![0, 80, 70, 0, 0],
+        vec![0, 20, 0, 130, 0],
+    ])
+    .unwrap();
+    TransportPlan {
+        total_cost: problem.calculate_total_cost(&allocations),
+        allocations,
+    }
+}
+
+#[test]
+fn recompute_cost_restores_consistency() {
+    let problem = TransportProblem::new();
+    let mut plan = problem.north_west_corner();
+    assert!(plan.cost_is_consistent(&problem));
+    let before = plan.total_cost;
+
+    // Ручной перенос 10 единиц A1 -> B1 на A1 -> B2
+    plan.allocations[0][0] -= 10;
+    plan.allocations[0][1] += 10;
+    assert!(!plan.cost_is_consistent(&problem));
+
+    plan.recompute_cost(&problem);
+    assert!(plan.cost_is_consistent(&problem));
+    assert_eq!(
+        plan.total_cost,
+        before - 10 * problem.costs[0][0] + 10 * problem.costs[0][1]
+    );
+}
+
+#[test]
+fn supplier_average_costs_on_built_in_optimum() {
+    let problem = TransportProblem::new();
+    let plan = built_in_optimum(&problem);
+    let averages = problem.supplier_avg_cost(&plan);
+
+    // A1: (90·12 + 110·17) / 200, A2: (80·8 + 70·15) / 150, A3: (20·16 + 130·12) / 150
+    let expected = [2950.0 / 200.0, 1690.0 / 150.0, 1880.0 / 150.0];
+    for (average, expected) in averages.iter().zip(expected) {
+        assert!((average.unwrap() - expected).abs() < 1e-9);
+    }
+
+    // Весь запас A2 остается у фиктивного потребителя
+    let surplus =
+        TransportProblem::try_new(vec![10, 10], vec![10], vec![vec![1], vec![5]]).unwrap();
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![vec![10, 0], vec![0, 10]]).unwrap(),
+        total_cost: 10,
+    };
+    assert_eq!(surplus.supplier_avg_cost(&plan), vec![Some(1.0), None]);
+}
+
+#[test]
+fn consumer_total_cost_sums_one_column() {
+    let problem = TransportProblem::new();
+    let plan = built_in_optimum(&problem);
+
+    // B2: 80 единиц от A2 по 8 и 20 от A3 по 16
+    assert_eq!(problem.consumer_total_cost(&plan, 1), Ok(80 * 8 + 20 * 16));
+    let all: Vec<i32> = (0..5)
+        .map(|j| problem.consumer_total_cost(&plan, j).unwrap())
+        .collect();
+    assert_eq!(all, problem.consumer_costs(&plan));
+
+    assert_eq!(
+        problem.consumer_total_cost(&plan, 5),
+        Err(TransportError::IndexOutOfRange { index: 5, len: 5 })
+    );
+}
+
+#[test]
+fn optimum_has_no_negative_allocations() {
+    let problem = TransportProblem::new();
+    assert_eq!(built_in_optimum(&problem).assert_nonnegative(), Ok(()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "отрицательные перевозки")]
+fn negative_allocation_panics_in_debug() {
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![vec![5, -1], vec![0, 6]]).unwrap(),
+        total_cost: 0,
+    };
+    let _ = plan.assert_nonnegative();
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn negative_allocation_stops_optimization_with_error() {
+    let problem =
+        TransportProblem::try_new(vec![5, 6], vec![5, 6], vec![vec![1, 9], vec![9, 1]]).unwrap();
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![vec![-10, 6], vec![6, 0]]).unwrap(),
+        total_cost: 0,
+    };
+    let result = problem.optimize_with_rule(plan, PivotRule::MostNegative);
+
+    assert!(!result.converged);
+    assert_eq!(
+        result.error,
+        Some(TransportError::NegativeAllocation {
+            cells: vec![(0, 0)]
+        })
+    );
+}
+
+#[test]
+fn least_used_routes_are_sorted_ascending() {
+    let problem = TransportProblem::new();
+    let plan = built_in_optimum(&problem);
+    let routes = problem.least_used_routes(&plan, 3);
+
+    // Наименьшие перевозки оптимума: 20, 70 и 80
+    assert_eq!(routes, vec![(2, 1, 20), (1, 2, 70), (1, 1, 80)]);
+    assert!(routes.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    assert_eq!(problem.least_used_routes(&plan, 100).len(), 6);
+}
+
+#[test]
+fn disconnected_basis_is_reported_not_zeroed() {
+    let problem = TransportProblem::new();
+    // Оптимум вырожден: A1 с B1 и B5 не связаны с остальными клетками
+    let plan = built_in_optimum(&problem);
+
+    assert_eq!(
+        problem.try_compute_potentials(&plan.allocations),
+        Err(PotentialError::Disconnected {
+            missing_rows: vec![1, 2],
+            missing_cols: vec![1, 2, 3],
+        })
+    );
+    assert!(
+        problem
+            .try_compute_potentials(&problem.north_west_corner().allocations)
+            .is_ok()
+    );
+
+    // Оценки и оптимизация достраивают базис эпсилон-клетками
+    assert!(problem.route_regret(&plan).is_ok());
+    let result = problem.optimize_with_rule(plan, PivotRule::MostNegative);
+    assert_eq!(result.error, None);
+    assert_eq!(result.plan.total_cost, 6520);
+}
+
+#[test]
+fn shipments_sum_to_total_cost() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    let shipments = plan.shipments(&problem);
+
+    assert_eq!(shipments.len(), plan.occupied_cells().count());
+    assert_eq!(
+        shipments.iter().map(|s| s.total).sum::<i32>(),
+        plan.total_cost
+    );
+}
+
+#[test]
+fn surplus_supplier_is_not_binding() {
+    let problem = TransportProblem::try_new(
+        vec![20, 30],
+        vec![10, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(problem.binding_constraints(&plan), (vec![0], vec![0, 1, 2]));
+}
+
+#[test]
+fn average_haul_is_ton_kilometers_per_unit() {
+    let problem =
+        TransportProblem::try_new(vec![10, 10], vec![5, 15], vec![vec![4, 2], vec![6, 8]]).unwrap();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(problem.total_units(&plan), 20);
+    assert_eq!(problem.ton_kilometers(&plan), 90);
+    assert_eq!(problem.average_haul(&plan), Some(4.5));
+}
+
+#[test]
+fn tie_break_prefers_fewer_routes() {
+    // Оба плана стоят 40: [[5, 5, 0], [0, 5, 10]] на четырех маршрутах
+    // и [[0, 10, 0], [5, 0, 10]] на трех
+    let problem = TransportProblem::try_new(
+        vec![10, 15],
+        vec![5, 10, 10],
+        vec![vec![2, 2, 2], vec![2, 2, 1]],
+    )
+    .unwrap();
+    let plan = problem.solve_minimize_routes();
+
+    assert_eq!(plan.total_cost, problem.solve_to_plan().total_cost);
+    assert_eq!(problem.basis_size(&plan), 3);
+    assert!(
+        problem
+            .validate_plan(&plan)
+            .iter()
+            .all(|issue| matches!(issue, PlanIssue::Degenerate { .. }))
+    );
+}
+
+#[test]
+fn histogram_counts_allocation_sizes() {
+    let problem = TransportProblem::new();
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![
+            vec![10, 0, 25, 0, 0],
+            vec![0, 12, 0, 40, 0],
+            vec![0, 0, 0, 31, 19],
+        ])
+        .unwrap(),
+        total_cost: 0,
+    };
+
+    assert_eq!(
+        problem.allocation_histogram(&plan, 3),
+        vec![(10, 20, 3), (21, 31, 2), (32, 42, 1)]
+    );
+    assert!(problem.allocation_histogram(&plan, 0).is_empty());
+}
+
+#[test]
+fn basic_cells_have_zero_regret() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    let regret = problem.route_regret(&plan).unwrap();
+
+    for (i, j, _) in plan.occupied_cells() {
+        assert_eq!(regret[i][j], 0.0);
+    }
+    // В оптимуме ни один маршрут не снижает стоимость
+    assert!(regret.iter().flatten().all(|&value| value >= -1e-9));
+}
+
+#[test]
+fn cost_contributions_sum_to_hundred_percent() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    let contributions = problem.cost_contributions(&plan);
+
+    assert_eq!(contributions.len(), plan.occupied_cells().count());
+    let total: f64 = contributions.iter().map(|&(_, _, share)| share).sum();
+    assert!((total - 100.0).abs() < 1e-9);
+    assert!(contributions.windows(2).all(|pair| pair[0].2 >= pair[1].2));
+
+    let free = TransportProblem::try_new(vec![5], vec![5], vec![vec![0]]).unwrap();
+    let free_plan = free.solve_to_plan();
+    assert_eq!(free.cost_contributions(&free_plan), vec![(0, 0, 0.0)]);
+}
+
+#[test]
+fn gap_report_explains_manual_plan() {
+    let problem = TransportProblem::new();
+    // План после первой итерации из условия задачи
+    let manual = TransportPlan {
+        allocations: Matrix::try_from(vec![
+            vec![90, 100, 10, 0, 0],
+            vec![0, 0, 60, 90, 0],
+            vec![0, 0, 0, 40, 110],
+        ])
+        .unwrap(),
+        total_cost: 0,
+    };
+    let report = problem.explain_vs(&manual);
+
+    assert_eq!(report.manual_cost, 7360);
+    assert_eq!(report.optimal_cost, 6520);
+    assert_eq!(report.gap(), 840);
+
+    // Применение перестановок к ручному плану дает оптимум
+    let mut fixed = manual.allocations.clone();
+    for &(i, j, change) in &report.reallocations {
+        fixed[i][j] += change;
+    }
+    assert_eq!(problem.calculate_total_cost(&fixed), report.optimal_cost);
+}
+
+#[test]
+fn identical_suppliers_are_coalesced() {
+    let problem = TransportProblem::try_new(
+        vec![30, 20, 25],
+        vec![40, 35],
+        vec![vec![4, 6], vec![5, 3], vec![4, 6]],
+    )
+    .unwrap();
+    let (reduced, groups) = problem.coalesce_identical_suppliers();
+
+    assert_eq!(groups, vec![vec![0, 2], vec![1]]);
+    assert_eq!(reduced.supplies, vec![55, 20]);
+    assert_eq!(reduced.costs.rows(), 2);
+
+    let plan = problem.expand_plan(&reduced.solve_to_plan(), &groups);
+    assert_eq!(plan.total_cost, problem.solve_to_plan().total_cost);
+    for i in 0..3 {
+        assert_eq!(plan.allocations.row_sum(i), problem.supplies[i]);
+    }
+    for j in 0..2 {
+        assert_eq!(plan.allocations.col_sum(j), problem.demands[j]);
+    }
+}
+
+#[test]
+fn load_balance_index_is_coefficient_of_variation() {
+    let problem = TransportProblem::try_new(
+        vec![10, 20, 30, 40],
+        vec![60],
+        vec![vec![1], vec![1], vec![1], vec![1]],
+    )
+    .unwrap();
+    let plan = TransportPlan {
+        allocations: Matrix::try_from(vec![vec![10, 0], vec![20, 0], vec![30, 0], vec![0, 40]])
+            .unwrap(),
+        total_cost: 0,
+    };
+
+    // Отгружено 10, 20, 30, 0 (A4 везет только фиктивному потребителю):
+    // среднее 15, стандартное отклонение √125
+    let expected = 125f64.sqrt() / 15.0;
+    assert!((problem.load_balance_index(&plan) - expected).abs() < 1e-12);
+
+    let even = TransportProblem::try_new(vec![5, 5], vec![10], vec![vec![1], vec![2]]).unwrap();
+    assert_eq!(even.load_balance_index(&even.solve_to_plan()), 0.0);
+}
+
+#[test]
+fn improving_cells_vanish_at_optimum() {
+    let problem = TransportProblem::new();
+    let start = problem.north_west_corner();
+    let cells = problem.improving_cells(&start);
+
+    assert!(!cells.is_empty());
+    assert!(cells.windows(2).all(|pair| pair[0].2 <= pair[1].2));
+    assert!(cells.iter().all(|&(_, _, delta)| delta < 0.0));
+    assert!(problem.improving_cells(&problem.solve_to_plan()).is_empty());
+}
+
+#[test]
+fn lexicographic_solution_prefers_cost_then_routes() {
+    // Оптимумы стоимостью 40: [[5, 5, 0], [0, 5, 10]] на четырех маршрутах
+    // и [[0, 10, 0], [5, 0, 10]] на трех
+    let problem = TransportProblem::try_new(
+        vec![10, 15],
+        vec![5, 10, 10],
+        vec![vec![2, 2, 2], vec![2, 2, 1]],
+    )
+    .unwrap();
+    let plan = problem.solve_lexicographic();
+
+    assert_eq!(plan.total_cost, 40);
+    assert_eq!(problem.basis_size(&plan), 3);
+    assert_eq!(
+        plan.allocations,
+        Matrix::try_from(vec![vec![0, 10, 0], vec![5, 0, 10]]).unwrap()
+    );
+    assert_eq!(plan, problem.solve_lexicographic());
+
+    // Оба плана на двух маршрутах стоят 10; выбирается меньшая матрица
+    let flat =
+        TransportProblem::try_new(vec![5, 5], vec![5, 5], vec![vec![1, 1], vec![1, 1]]).unwrap();
+    assert_eq!(
+        flat.solve_lexicographic().allocations,
+        Matrix::try_from(vec![vec![0, 5], vec![5, 0]]).unwrap()
+    );
+}
+
+#[test]
+fn most_expensive_consumer_on_built_in_optimum() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+
+    // B5 получает 110 единиц от A1 по 17
+    assert_eq!(problem.most_expensive_consumer(&plan), (4, 17.0));
+    let averages = problem.consumer_avg_cost(&plan);
+    assert_eq!(averages.len(), 5);
+    assert!(averages.iter().all(|cost| cost.is_some_and(|c| c <= 17.0)));
+}
+
+#[test]
+fn min_reduced_cost_is_non_negative_at_optimum() {
+    let problem = TransportProblem::new();
+
+    assert!(problem.min_reduced_cost(&problem.solve_to_plan()) >= -1e-9);
+    // У плана северо-западного угла лучшая клетка A1 -> B5 с оценкой -8
+    assert_eq!(problem.min_reduced_cost(&problem.north_west_corner()), -8.0);
+
+    let single = TransportProblem::try_new(vec![5], vec![5], vec![vec![3]]).unwrap();
+    assert_eq!(
+        single.min_reduced_cost(&single.solve_to_plan()),
+        f64::INFINITY
+    );
+}
+
+#[test]
+fn regional_costs_sum_by_region_pair() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    // Юг: A1; север: A2, A3. Потребители: B1, B2 на юге, остальные на севере
+    let regions = problem
+        .regional_costs(&plan, &[0, 1, 1], &[0, 0, 1, 1, 1])
+        .unwrap();
+
+    // A1: 90·12 на юг, 110·17 на север; A2: 80·8 на юг, 70·15 на север;
+    // A3: 20·16 на юг, 130·12 на север
+    assert_eq!(regions.len(), 4);
+    assert_eq!(regions[&(0, 0)], 1080);
+    assert_eq!(regions[&(0, 1)], 1870);
+    assert_eq!(regions[&(1, 0)], 640 + 320);
+    assert_eq!(regions[&(1, 1)], 1050 + 1560);
+    assert_eq!(regions.values().sum::<i32>(), plan.total_cost);
+
+    assert_eq!(
+        problem.regional_costs(&plan, &[0, 1], &[0, 0, 1, 1, 1]),
+        Err(TransportError::DimensionMismatch {
+            expected: 3,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn unique_optimum_is_recognized() {
+    let unique =
+        TransportProblem::try_new(vec![10, 20], vec![15, 15], vec![vec![1, 4], vec![3, 2]])
+            .unwrap();
+    assert!(unique.has_unique_optimum());
+
+    // Любой допустимый план стоит 90
+    let flat = TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 4]])
+        .unwrap();
+    assert!(!flat.has_unique_optimum());
+}
+
+#[test]
+fn fill_rate_reports_shortage() {
+    // Запасов 30 при потребности 40: четверть потребности не закрыта
+    let short = TransportProblem::try_new(
+        vec![10, 20],
+        vec![15, 15, 10],
+        vec![vec![2, 3, 1], vec![5, 4, 8]],
+    )
+    .unwrap();
+    let plan = short.solve_to_plan();
+    assert_eq!(plan.allocations.rows(), 3);
+    assert_eq!(short.fill_rate(&plan), 0.75);
+
+    let problem = TransportProblem::new();
+    assert_eq!(problem.fill_rate(&problem.solve_to_plan()), 1.0);
+}
+
+#[test]
+fn percentages_are_normalized_by_demand() {
+    let problem = TransportProblem::new();
+    let percentages = problem.plan_as_percentages(&problem.solve_to_plan());
+
+    assert_eq!(percentages.len(), 3);
+    for j in 0..problem.demands.len() {
+        let total: f64 = percentages.iter().map(|row| row[j]).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+    }
+
+    let idle =
+        TransportProblem::try_new(vec![10, 5], vec![15, 0], vec![vec![1, 2], vec![3, 4]]).unwrap();
+    let percentages = idle.plan_as_percentages(&idle.solve_to_plan());
+    assert!((percentages[0][0] - 200.0 / 3.0).abs() < 1e-9);
+    assert_eq!(percentages[0][1], 0.0);
+    assert_eq!(percentages[1][1], 0.0);
+}
@@ -0,0 +1,66 @@
+use transport_problem::{Matrix, TransportError, TransportPlan, TransportProblem};
+
+#[test]
+fn fractional_optimum_rounds_to_balanced_plan() {
+    // Стоимости вида a_i + b_j: любой допустимый план оптимален,
+    // в том числе дробный с полутора единицами в каждой клетке
+    let problem = TransportProblem::try_new(
+        vec![3, 3, 3],
+        vec![3, 3, 3],
+        vec![vec![2, 3, 5], vec![4, 5, 7], vec![1, 2, 4]],
+    )
+    .unwrap();
+    let fractional = vec![
+        vec![1.5, 1.5, 0.0],
+        vec![1.5, 0.0, 1.5],
+        vec![0.0, 1.5, 1.5],
+    ];
+
+    let (plan, delta) = problem.round_to_integer(&fractional).unwrap();
+
+    for i in 0..3 {
+        assert_eq!(plan.allocations.row_sum(i), problem.supplies[i]);
+        assert_eq!(plan.allocations.col_sum(i), problem.demands[i]);
+        for j in 0..3 {
+            let value = plan.allocations[i][j] as f64;
+            assert!((value - fractional[i][j]).abs() < 1.0);
+        }
+    }
+    assert!(delta.abs() < 1e-9);
+    assert_eq!(plan.total_cost, problem.solve_to_plan().total_cost);
+}
+
+#[test]
+fn plan_too_far_from_balance_is_rejected() {
+    let problem =
+        TransportProblem::try_new(vec![3, 1], vec![2, 2], vec![vec![1, 2], vec![3, 4]]).unwrap();
+    // Первой строке не хватает трех единиц, а поднять на единицу можно
+    // лишь две ее клетки
+    let fractional = vec![vec![0.5, 0.5], vec![0.5, 0.5]];
+
+    assert_eq!(
+        problem.round_to_integer(&fractional),
+        Err(TransportError::Infeasible)
+    );
+}
+
+#[test]
+fn plan_round_trips_through_f64() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(TransportPlan::from_f64(&plan.to_f64(), &problem), Ok(plan));
+
+    // Половины округляются от нуля
+    let fractional = vec![vec![0.5, 1.49], vec![2.5, -0.4]];
+    let square =
+        TransportProblem::try_new(vec![2, 2], vec![2, 2], vec![vec![1, 1], vec![1, 1]]).unwrap();
+    let rounded = TransportPlan::from_f64(&fractional, &square).unwrap();
+    assert_eq!(
+        rounded.allocations,
+        Matrix::try_from(vec![vec![1, 1], vec![3, 0]]).unwrap()
+    );
+    assert_eq!(rounded.total_cost, 5);
+
+    assert!(TransportPlan::from_f64(&[vec![1.0]], &square).is_err());
+}
@@ -0,0 +1,140 @@
+use transport_problem::{FORBIDDEN, TransportError, TransportProblem};
+
+#[test]
+fn dominated_route_is_reported() {
+    // A2 -> B1 по 50: A1 один закрывает B1, а обмен с любой другой
+    // перевозкой A1 только дешевле
+    let problem = TransportProblem::try_new(
+        vec![20, 20, 10],
+        vec![15, 20, 15],
+        vec![vec![2, 4, 5], vec![50, 3, 6], vec![4, 5, FORBIDDEN]],
+    )
+    .unwrap();
+    let dominated = problem.dominated_routes();
+
+    assert!(dominated.contains(&(1, 0)));
+    assert!(!dominated.contains(&(2, 2)));
+}
+
+#[test]
+fn built_in_optimum_uses_no_dominated_route() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+    let dominated = problem.dominated_routes();
+
+    assert_eq!(dominated, vec![(0, 1), (0, 3), (1, 4), (2, 0), (2, 2)]);
+    assert!(
+        plan.occupied_cells()
+            .all(|(i, j, _)| !dominated.contains(&(i, j)))
+    );
+}
+
+#[test]
+fn cheapest_supplier_per_consumer_on_built_in_matrix() {
+    // Минимумы столбцов: 12, 8, 15, 11, 17
+    assert_eq!(
+        TransportProblem::new().cheapest_supplier_per_consumer(),
+        vec![0, 1, 1, 1, 0]
+    );
+
+    let blocked =
+        TransportProblem::try_new(vec![5, 5], vec![5, 5], vec![vec![FORBIDDEN, 5], vec![9, 3]])
+            .unwrap();
+    assert_eq!(blocked.cheapest_supplier_per_consumer(), vec![1, 1]);
+}
+
+#[test]
+fn cost_ranges_on_built_in_matrix() {
+    let problem = TransportProblem::new();
+
+    assert_eq!(
+        problem.column_cost_range(),
+        vec![(12, 19), (8, 16), (15, 26), (11, 14), (17, 21)]
+    );
+    assert_eq!(problem.row_cost_range(), vec![(12, 21), (8, 21), (12, 26)]);
+}
+
+#[test]
+fn cost_ranges_skip_forbidden_routes() {
+    let (problem, _) =
+        TransportProblem::from_ragged(vec![10, 10], vec![5, 5, 10], vec![vec![4, 6, 3], vec![5]])
+            .unwrap();
+
+    assert_eq!(problem.column_cost_range(), vec![(4, 5), (6, 6), (3, 3)]);
+    assert_eq!(problem.row_cost_range(), vec![(3, 6), (5, 5)]);
+    assert_eq!(problem.cost_magnitude(), (3, 6));
+
+    let blocked = TransportProblem::try_new(vec![5], vec![5, 5], vec![vec![2, FORBIDDEN]]).unwrap();
+    assert_eq!(blocked.column_cost_range(), vec![(2, 2), (0, 0)]);
+}
+
+#[test]
+fn forced_routes_respect_threshold() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![10, 10, 10],
+        vec![vec![1, 5, FORBIDDEN], vec![9, 6, FORBIDDEN], vec![8, 6, 3]],
+    )
+    .unwrap();
+
+    assert_eq!(problem.forced_routes(5), vec![(0, 0), (2, 2)]);
+    assert_eq!(problem.forced_routes(1), vec![(0, 0), (0, 1), (2, 2)]);
+}
+
+#[test]
+fn route_limit_trades_cost_for_fewer_lanes() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10],
+        vec![10, 5, 5],
+        vec![vec![1, 1, 5], vec![1, 5, 1]],
+    )
+    .unwrap();
+    let optimum = problem.solve_to_plan();
+    assert_eq!(optimum.total_cost, 20);
+    assert_eq!(problem.basis_size(&optimum), 4);
+
+    let limited = problem.solve_with_max_routes(3).unwrap();
+    assert_eq!(problem.basis_size(&limited), 3);
+    assert_eq!(limited.total_cost, 40);
+
+    assert_eq!(
+        problem.solve_with_max_routes(2),
+        Err(TransportError::Infeasible)
+    );
+}
+
+#[test]
+fn tied_suppliers_are_all_returned() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![15, 15],
+        vec![vec![4, 2], vec![3, 7], vec![3, 2]],
+    )
+    .unwrap();
+
+    assert_eq!(problem.tied_cheapest_suppliers(0), vec![1, 2]);
+    assert_eq!(problem.tied_cheapest_suppliers(1), vec![0, 2]);
+    assert!(problem.tied_cheapest_suppliers(2).is_empty());
+}
+
+#[test]
+fn row_column_reduction_of_built_in_matrix() {
+    let problem = TransportProblem::new();
+
+    assert_eq!(
+        problem.row_column_reduced_costs(),
+        vec![
+            vec![0, 3, 2, 2, 0],
+            vec![6, 0, 0, 3, 8],
+            vec![7, 4, 7, 0, 3],
+        ]
+    );
+
+    let blocked =
+        TransportProblem::try_new(vec![5, 5], vec![5, 5], vec![vec![4, FORBIDDEN], vec![6, 9]])
+            .unwrap();
+    assert_eq!(
+        blocked.row_column_reduced_costs(),
+        vec![vec![0, FORBIDDEN], vec![0, 0]]
+    );
+}
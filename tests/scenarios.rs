@@ -0,0 +1,108 @@
+use std::sync::mpsc;
+use std::thread;
+use std::time::Instant;
+
+use transport_problem::{IterationStep, SolveEvent, TransportError, TransportProblem};
+
+#[test]
+fn scenarios_with_different_demands_have_different_optima() {
+    let problem = TransportProblem::new();
+    let shifted = vec![110, 100, 70, 130, 90];
+    let results = problem.solve_scenarios(&[problem.demands.clone(), shifted.clone(), vec![500]]);
+
+    assert_eq!(results.len(), 3);
+    let base = results[0].as_ref().unwrap();
+    let other = results[1].as_ref().unwrap();
+    assert_eq!(base.total_cost, problem.solve_to_plan().total_cost);
+
+    let expected = TransportProblem {
+        demands: shifted,
+        ..problem.clone()
+    }
+    .solve_to_plan();
+    assert_eq!(other.allocations, expected.allocations);
+    assert_eq!(other.total_cost, expected.total_cost);
+    assert_ne!(other.total_cost, base.total_cost);
+
+    assert_eq!(
+        results[2].as_ref().err(),
+        Some(&TransportError::DimensionMismatch {
+            expected: 5,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn parallel_scenarios_match_serial() {
+    let problem = TransportProblem::new();
+    let demand_sets: Vec<Vec<i32>> = (0..40)
+        .map(|k| vec![90 + k, 100, 70 - k, 130, 110 + k % 7])
+        .collect();
+
+    let serial = problem.solve_scenarios(&demand_sets);
+    let parallel = problem.solve_scenarios_parallel(&demand_sets);
+
+    assert_eq!(serial, parallel);
+}
+
+// Замер ускорения: cargo test --release -- --ignored --nocapture
+#[test]
+#[ignore]
+fn parallel_scenarios_speedup() {
+    let n = 30;
+    let costs: Vec<Vec<i32>> = (0..n)
+        .map(|i| (0..n).map(|j| (i * 37 + j * 91) as i32 % 97 + 1).collect())
+        .collect();
+    let problem = TransportProblem::try_new(vec![100; n], vec![100; n], costs).unwrap();
+    let demand_sets: Vec<Vec<i32>> = (0..400)
+        .map(|k| {
+            (0..n)
+                .map(|j| 80 + ((j * 13 + k * 7) % 41) as i32)
+                .collect()
+        })
+        .collect();
+
+    let start = Instant::now();
+    let serial = problem.solve_scenarios(&demand_sets);
+    let serial_time = start.elapsed();
+    let start = Instant::now();
+    let parallel = problem.solve_scenarios_parallel(&demand_sets);
+    let parallel_time = start.elapsed();
+
+    assert_eq!(serial, parallel);
+    println!(
+        "{} сценариев: последовательно {:?}, параллельно {:?}, ускорение {:.2}",
+        demand_sets.len(),
+        serial_time,
+        parallel_time,
+        serial_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
+}
+
+#[test]
+fn streaming_events_arrive_in_order() {
+    let problem = TransportProblem::new();
+    let (tx, rx) = mpsc::channel();
+    let solver = {
+        let problem = problem.clone();
+        thread::spawn(move || problem.solve_streaming(tx))
+    };
+    let events: Vec<SolveEvent> = rx.iter().collect();
+    solver.join().unwrap();
+
+    let (first, rest) = events.split_first().unwrap();
+    let (last, pivots) = rest.split_last().unwrap();
+    assert_eq!(*first, SolveEvent::InitialPlan(problem.north_west_corner()));
+    assert_eq!(*last, SolveEvent::Optimal(problem.solve_to_plan()));
+
+    let (_, history) = problem.optimize_with_history(problem.north_west_corner());
+    let steps: Vec<IterationStep> = pivots
+        .iter()
+        .map(|event| match event {
+            SolveEvent::Pivot(step) => step.clone(),
+            other => panic!("ожидался поворот, получено {:?}", other),
+        })
+        .collect();
+    assert_eq!(steps, history);
+}
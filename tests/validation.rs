@@ -0,0 +1,241 @@
+use transport_problem::{FORBIDDEN, PlanIssue, TransportError, TransportProblem};
+
+#[test]
+fn ragged_rows_are_padded_with_forbidden_routes() {
+    let (problem, padded) = TransportProblem::from_ragged(
+        vec![20, 10, 15],
+        vec![15, 15, 15],
+        vec![vec![4, 6, 3], vec![5], vec![2, 7]],
+    )
+    .unwrap();
+
+    assert_eq!(padded, vec![1, 2]);
+    assert_eq!(problem.costs[1], vec![5, FORBIDDEN, FORBIDDEN]);
+    assert_eq!(problem.costs[2], vec![2, 7, FORBIDDEN]);
+    assert!(problem.is_forbidden(2, 2));
+    assert!(!problem.is_forbidden(2, 1));
+
+    // Слишком длинная строка не обрезается, а отклоняется
+    assert!(matches!(
+        TransportProblem::from_ragged(vec![5], vec![5], vec![vec![1, 2]]),
+        Err(TransportError::RaggedRow { row: 0, .. })
+    ));
+}
+
+#[test]
+fn assignment_problem_is_detected() {
+    let assignment = TransportProblem::try_new(
+        vec![1, 1, 1],
+        vec![1, 1, 1],
+        vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]],
+    )
+    .unwrap();
+    assert!(assignment.is_assignment());
+
+    assert!(!TransportProblem::new().is_assignment());
+    let rectangular = TransportProblem::try_new(
+        vec![1, 1],
+        vec![1, 1, 1],
+        vec![vec![1, 2, 3], vec![4, 5, 6]],
+    )
+    .unwrap();
+    assert!(!rectangular.is_assignment());
+}
+
+#[test]
+fn asymmetric_costs_are_reported() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![10, 10, 10],
+        vec![vec![0, 5, 7], vec![5, 0, 3], vec![8, 3, 0]],
+    )
+    .unwrap();
+
+    assert_eq!(problem.assert_symmetric_costs(), Err(vec![(0, 2)]));
+}
+
+#[test]
+fn triangle_inequality_violations_are_reported() {
+    // Прямой путь между 1 и 3 стоит 9, через 2 — всего 2 + 3
+    let detour = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![10, 10, 10],
+        vec![vec![0, 2, 9], vec![2, 0, 3], vec![9, 3, 0]],
+    )
+    .unwrap();
+    assert_eq!(
+        detour.violates_triangle_inequality(),
+        vec![(0, 1, 2), (2, 1, 0)]
+    );
+
+    let metric = TransportProblem::try_new(
+        vec![10, 10, 10],
+        vec![10, 10, 10],
+        vec![vec![0, 5, 7], vec![5, 0, 3], vec![7, 3, 0]],
+    )
+    .unwrap();
+    assert!(metric.violates_triangle_inequality().is_empty());
+    assert!(
+        TransportProblem::new()
+            .violates_triangle_inequality()
+            .is_empty()
+    );
+}
+
+#[test]
+fn empty_problem_is_rejected() {
+    assert_eq!(
+        TransportProblem::try_new(vec![], vec![10], vec![]),
+        Err(TransportError::Empty)
+    );
+    assert_eq!(
+        TransportProblem::try_new(vec![10], vec![], vec![vec![]]),
+        Err(TransportError::Empty)
+    );
+}
+
+#[test]
+fn all_zero_totals_are_rejected() {
+    assert_eq!(
+        TransportProblem::try_new(vec![0, 0], vec![5], vec![vec![1], vec![2]]),
+        Err(TransportError::ZeroTotal)
+    );
+    assert_eq!(
+        TransportProblem::try_new(vec![5], vec![0, 0], vec![vec![1, 2]]),
+        Err(TransportError::ZeroTotal)
+    );
+}
+
+#[test]
+fn reference_plans_are_checked_for_feasibility() {
+    let problem = TransportProblem::new();
+    let after_first = problem.from_reference_plan(
+        vec![
+            vec![90, 100, 10, 0, 0],
+            vec![0, 0, 60, 90, 0],
+            vec![0, 0, 0, 40, 110],
+        ],
+        "План после 1 итерации",
+    );
+    let improved = problem.from_reference_plan(
+        vec![
+            vec![90, 100, 0, 0, 10],
+            vec![0, 0, 70, 80, 0],
+            vec![0, 0, 0, 50, 100],
+        ],
+        "Улучшенный план",
+    );
+    let short = problem.from_reference_plan(vec![vec![200, 0, 0, 0, 0]], "Неполный");
+
+    assert!(after_first.is_feasible());
+    assert_eq!(after_first.plan.total_cost, 7360);
+    assert!(improved.is_feasible());
+    assert_eq!(improved.plan.total_cost, 7280);
+    assert_eq!(
+        short.issues,
+        vec![PlanIssue::Dimensions {
+            expected: (3, 5),
+            found: (1, 5)
+        }]
+    );
+
+    let report = problem.analyze(&[after_first, improved, short]).to_string();
+    assert!(report.contains("План после 1 итерации: 7360 у.е. (отклонение от оптимума: 840)"));
+    assert!(report.contains("Неполный: недопустимый план:"));
+}
+
+#[test]
+fn forbidden_route_in_plan_is_caught() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10],
+        vec![10, 10],
+        vec![vec![1, FORBIDDEN], vec![2, 3]],
+    )
+    .unwrap();
+
+    assert_eq!(
+        problem.try_calculate_total_cost(&[vec![0, 10], vec![10, 0]]),
+        Err(TransportError::ForbiddenRouteUsed { i: 0, j: 1 })
+    );
+    assert_eq!(
+        problem.try_calculate_total_cost(&[vec![10, 0], vec![0, 10]]),
+        Ok(40)
+    );
+}
+
+#[test]
+fn ratios_build_balanced_problem() {
+    let problem = TransportProblem::from_ratios(
+        1000,
+        &[1.0, 1.0, 1.0],
+        &[0.5, 0.3, 0.2],
+        vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]],
+    )
+    .unwrap();
+
+    assert!(problem.is_balanced());
+    assert_eq!(problem.supplies, vec![334, 333, 333]);
+    assert_eq!(problem.demands, vec![500, 300, 200]);
+
+    assert_eq!(
+        TransportProblem::from_ratios(100, &[1.0, -1.0], &[1.0], vec![vec![1], vec![2]]),
+        Err(TransportError::ZeroTotal)
+    );
+}
+
+#[test]
+fn negative_cost_is_found_and_can_be_rejected() {
+    let problem =
+        TransportProblem::try_new(vec![10, 10], vec![10, 10], vec![vec![3, 4], vec![-2, 5]])
+            .unwrap();
+
+    assert_eq!(problem.has_negative_costs(), Some((1, 0)));
+    assert_eq!(
+        problem.clone().reject_negative_costs(),
+        Err(TransportError::NegativeCost { i: 1, j: 0 })
+    );
+    assert!(TransportProblem::new().reject_negative_costs().is_ok());
+
+    let mut report = Vec::new();
+    problem.solve_to_writer(&mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+    assert!(report.contains("Внимание: отрицательная стоимость A2 -> B1: -2"));
+}
+
+#[test]
+fn evaluate_checks_allocation_dimensions() {
+    let problem = TransportProblem::new();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(problem.evaluate(&plan.allocations), Ok(plan.total_cost));
+    assert_eq!(
+        problem.evaluate(&[vec![90, 100, 10, 0, 0], vec![0, 0, 60, 90, 0]]),
+        Err(TransportError::DimensionMismatch {
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(
+        problem.evaluate(&[vec![90, 100], vec![0, 0], vec![0, 0]]),
+        Err(TransportError::RaggedRow {
+            row: 0,
+            expected: 5,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn strict_solve_rejects_degenerate_start() {
+    // Северо-западный угол закрывает A1 и B1 одновременно: 2 клетки из 3
+    let degenerate =
+        TransportProblem::try_new(vec![10, 20], vec![10, 20], vec![vec![1, 2], vec![3, 1]])
+            .unwrap();
+    assert_eq!(
+        degenerate.solve_strict(),
+        Err(TransportError::Degenerate { missing: 1 })
+    );
+
+    let problem = TransportProblem::new();
+    assert_eq!(problem.solve_strict(), Ok(problem.solve_to_plan()));
+}
@@ -0,0 +1,338 @@
+use transport_problem::{FORBIDDEN, Matrix, TransportError, TransportProblem};
+
+#[test]
+fn cheap_new_supplier_lowers_optimum() {
+    let problem = TransportProblem::new();
+
+    let cheap = problem.evaluate_new_supplier(100, vec![1; 5]).unwrap();
+    assert!(cheap < 6520);
+    // Дорогой поставщик остается без отгрузок, оптимум прежний
+    assert_eq!(problem.evaluate_new_supplier(100, vec![50; 5]), Ok(6520));
+    assert_eq!(
+        problem.evaluate_new_supplier(100, vec![1; 4]),
+        Err(TransportError::DimensionMismatch {
+            expected: 5,
+            found: 4
+        })
+    );
+}
+
+#[test]
+fn fixed_charges_steer_plan_off_linear_optimum() {
+    let plain = TransportProblem::try_new(vec![10, 10], vec![10, 10], vec![vec![1, 2], vec![2, 1]])
+        .unwrap();
+    let charged = plain
+        .clone()
+        .with_fixed_costs(vec![vec![100, 0], vec![0, 100]])
+        .unwrap();
+
+    // Без доплат эвристика совпадает с линейным оптимумом
+    assert_eq!(plain.solve_fixed_charge().total_cost, 20);
+
+    // Линейный оптимум по диагонали платит обе доплаты: 20 + 200
+    let linear = plain.solve_to_plan();
+    assert_eq!(charged.calculate_total_cost(&linear.allocations), 220);
+    let plan = charged.solve_fixed_charge();
+    assert_eq!(
+        plan.allocations,
+        Matrix::try_from(vec![vec![0, 10], vec![10, 0]]).unwrap()
+    );
+    assert_eq!(plan.total_cost, 40);
+}
+
+#[test]
+fn supply_marginal_matches_re_solve() {
+    let problems = [
+        TransportProblem::new(),
+        // Дефицит: лишняя единица сокращает недопоставку
+        TransportProblem::try_new(
+            vec![50, 60],
+            vec![40, 40, 40],
+            vec![vec![2, 3, 1], vec![5, 4, 8]],
+        )
+        .unwrap(),
+        TransportProblem::try_new(
+            vec![20, 30],
+            vec![10, 15, 10],
+            vec![vec![2, 3, 1], vec![5, 4, 8]],
+        )
+        .unwrap(),
+    ];
+
+    for problem in problems {
+        let plan = problem.solve_to_plan();
+        let marginals = problem.supply_marginals(&plan);
+        for i in 0..problem.supplies.len() {
+            let mut grown = problem.clone();
+            grown.supplies[i] += 1;
+            let actual = grown.solve_to_plan().total_cost - plan.total_cost;
+            assert_eq!(marginals[i], actual as f64);
+        }
+    }
+
+    let problem = TransportProblem::new();
+    assert_eq!(
+        problem.supply_marginals(&problem.solve_to_plan()),
+        vec![-2.0, -8.0, 0.0]
+    );
+}
+
+#[test]
+fn fixed_route_never_beats_the_optimum() {
+    let problem = TransportProblem::new();
+    let optimum = problem.solve_to_plan();
+
+    // Закрепление маршрута на его оптимальном объеме оптимум не меняет
+    assert_eq!(
+        problem.optimum_with_route_fixed(0, 0, 90),
+        Ok(optimum.total_cost)
+    );
+    // Перевозка по дорогому маршруту только ухудшает план
+    let forced = problem.optimum_with_route_fixed(2, 2, 50).unwrap();
+    assert!(forced > optimum.total_cost);
+}
+
+#[test]
+fn supplier_outage_raises_cost() {
+    let problem = TransportProblem::try_new(
+        vec![30, 30, 30],
+        vec![25, 35],
+        vec![vec![1, 4], vec![3, 2], vec![5, 5]],
+    )
+    .unwrap();
+    assert_eq!(problem.solve_to_plan().total_cost, 105);
+
+    assert_eq!(problem.cost_without_supplier(0), Ok(210));
+    assert_eq!(problem.cost_without_supplier(1), Ok(195));
+    // Резервный поставщик в оптимуме не участвует
+    assert_eq!(problem.cost_without_supplier(2), Ok(105));
+    assert_eq!(
+        problem.cost_without_supplier(3),
+        Err(TransportError::IndexOutOfRange { index: 3, len: 3 })
+    );
+}
+
+#[test]
+fn outage_without_spare_capacity_is_infeasible() {
+    let problem = TransportProblem::new();
+    assert_eq!(
+        problem.cost_without_supplier(0),
+        Err(TransportError::Infeasible)
+    );
+}
+
+#[test]
+fn discount_goes_to_the_most_valuable_route() {
+    let problem = TransportProblem::try_new(
+        vec![30, 30, 30],
+        vec![25, 35],
+        vec![vec![1, 4], vec![3, 2], vec![5, 5]],
+    )
+    .unwrap();
+
+    // Оптимум 105; скидка 2 на A2 -> B2 экономит на всех 30 единицах этого
+    // маршрута (45), а на A1 -> B1 — только на 25 (80)
+    assert_eq!(problem.most_valuable_cost_reduction(2), Some((1, 1, 45)));
+}
+
+#[test]
+fn added_consumer_takes_surplus() {
+    let mut problem =
+        TransportProblem::try_new(vec![20, 30], vec![20], vec![vec![1], vec![3]]).unwrap();
+    assert_eq!(problem.solve_to_plan().total_cost, 20);
+
+    problem.add_consumer(25, vec![4, 2]).unwrap();
+    assert_eq!(problem.demands, vec![20, 25]);
+    assert_eq!(problem.solve_to_plan().total_cost, 20 + 50);
+
+    assert_eq!(
+        problem.add_consumer(5, vec![1]),
+        Err(TransportError::DimensionMismatch {
+            expected: 2,
+            found: 1
+        })
+    );
+}
+
+#[test]
+fn shortfall_goes_to_supplier_nearest_unmet_demand() {
+    // Запасов 30 при потребности 45; в оптимуме не довозится 5 единиц до B1
+    // и 10 до B2, второму поставщику это обойдется дешевле (50 против 65)
+    let problem =
+        TransportProblem::try_new(vec![20, 10], vec![25, 20], vec![vec![1, 6], vec![4, 3]])
+            .unwrap();
+
+    assert_eq!(problem.min_supply_to_balance(), (15, 1));
+    assert_eq!(TransportProblem::new().min_supply_to_balance(), (0, 0));
+}
+
+#[test]
+fn demand_elasticity_matches_manual_resolve() {
+    let problem = TransportProblem::new();
+    // +50 к потребности 500 — это +10% каждому потребителю и поставщику
+    let scaled = TransportProblem::try_new(
+        vec![220, 165, 165],
+        vec![99, 110, 77, 143, 121],
+        problem.costs.to_vec(),
+    )
+    .unwrap();
+    let expected =
+        (scaled.solve_to_plan().total_cost - problem.solve_to_plan().total_cost) as f64 / 50.0;
+
+    assert_eq!(problem.demand_elasticity(50), expected);
+    assert_eq!(problem.demand_elasticity(0), 0.0);
+}
+
+#[test]
+fn critical_route_has_largest_removal_cost() {
+    let problem = TransportProblem::try_new(
+        vec![30, 30, 30],
+        vec![25, 35],
+        vec![vec![1, 4], vec![3, 2], vec![5, 5]],
+    )
+    .unwrap();
+    let plan = problem.solve_to_plan();
+
+    // Оптимум 105; запрет A1 -> B1 поднимает его до 205, A2 -> B2 — до 195,
+    // A1 -> B2 — до 110
+    assert_eq!(problem.most_critical_route(&plan), (0, 0, 100));
+}
+
+#[test]
+fn route_without_alternative_is_critical() {
+    let problem = TransportProblem::try_new(
+        vec![10, 10],
+        vec![10, 10],
+        vec![vec![1, FORBIDDEN], vec![2, 3]],
+    )
+    .unwrap();
+    let plan = problem.solve_to_plan();
+
+    assert_eq!(problem.most_critical_route(&plan), (0, 0, i32::MAX));
+}
+
+#[test]
+fn capacity_limit_saturates_cheapest_route() {
+    // Без ограничения A1 -> B2 везет 20 единиц при стоимости 100
+    let problem =
+        TransportProblem::try_new(vec![20, 20], vec![20, 20], vec![vec![1, 3], vec![2, 6]])
+            .unwrap()
+            .with_route_capacities(vec![vec![0, 15], vec![0, 0]])
+            .unwrap();
+    assert_eq!(problem.solve_to_plan().total_cost, 100);
+
+    let plan = problem.solve_capacitated().unwrap();
+    assert_eq!(plan.allocations[0][1], 15);
+    assert_eq!(plan.total_cost, 110);
+    assert_eq!(problem.route_utilization(&plan)[0][1], 1.0);
+    assert_eq!(problem.route_utilization(&plan)[0][0], 0.0);
+    assert_eq!(problem.saturated_routes(&plan), vec![(0, 1)]);
+
+    let mut report = Vec::new();
+    problem.solve_to_writer(&mut report).unwrap();
+    assert!(
+        String::from_utf8(report)
+            .unwrap()
+            .contains("Примечание: маршруты загружены полностью: A1 -> B2")
+    );
+
+    let narrow = TransportProblem::try_new(vec![10], vec![10], vec![vec![1]])
+        .unwrap()
+        .with_route_capacities(vec![vec![5]])
+        .unwrap();
+    assert_eq!(narrow.solve_capacitated(), Err(TransportError::Infeasible));
+}
+
+#[test]
+fn swapped_suppliers_keep_optimum() {
+    let problem = TransportProblem::new();
+    let original = problem.solve_to_plan().total_cost;
+
+    // Обмен строками вместе с запасами лишь перенумеровывает поставщиков
+    assert_eq!(problem.cost_with_swapped_suppliers(0, 1), original);
+    assert_eq!(problem.cost_with_swapped_suppliers(0, 2), original);
+    assert_eq!(problem.cost_with_swapped_suppliers(1, 1), original);
+}
+
+#[test]
+#[should_panic(expected = "индекс поставщика")]
+fn swapped_suppliers_reject_bad_index() {
+    TransportProblem::new().cost_with_swapped_suppliers(0, 3);
+}
+
+#[test]
+fn consumer_marginal_matches_re_solve() {
+    let problems = [
+        // Оптимум вырожден: шесть занятых клеток вместо семи
+        TransportProblem::new(),
+        TransportProblem::try_new(
+            vec![20, 30],
+            vec![10, 15, 10],
+            vec![vec![2, 3, 1], vec![5, 4, 8]],
+        )
+        .unwrap(),
+        TransportProblem::try_new(
+            vec![20, 10],
+            vec![10, 15, 10],
+            vec![vec![2, 3, 1], vec![5, 4, 8]],
+        )
+        .unwrap(),
+    ];
+
+    for problem in problems {
+        let plan = problem.solve_to_plan();
+        for j in 0..problem.demands.len() {
+            let mut grown = problem.clone();
+            grown.demands[j] += 1;
+            let actual = grown.solve_to_plan().total_cost - plan.total_cost;
+            assert_eq!(
+                problem.marginal_cost_to_consumer(&plan, j).unwrap(),
+                actual as f64
+            );
+        }
+    }
+
+    let problem = TransportProblem::new();
+    assert!(
+        problem
+            .marginal_cost_to_consumer(&problem.solve_to_plan(), 5)
+            .is_err()
+    );
+}
+
+#[test]
+fn multiperiod_carries_cheap_supply_forward() {
+    let first = TransportProblem::try_new(vec![30, 10], vec![20], vec![vec![1], vec![5]]).unwrap();
+    let second = TransportProblem::try_new(vec![0, 30], vec![30], vec![vec![1], vec![6]]).unwrap();
+
+    // Остаток A1 из первого периода доходит во второй по 1 + 2 < 6
+    let plans = first
+        .solve_multiperiod(std::slice::from_ref(&second), 2)
+        .unwrap();
+    assert_eq!(plans.len(), 2);
+    assert_eq!(
+        plans[0].allocations,
+        Matrix::try_from(vec![vec![20], vec![0]]).unwrap()
+    );
+    assert_eq!(
+        plans[1].allocations,
+        Matrix::try_from(vec![vec![10], vec![20]]).unwrap()
+    );
+    assert_eq!(plans[0].total_cost + plans[1].total_cost, 20 + 30 + 120);
+
+    // Дорогое хранение: второй период закрывает A2
+    let plans = first
+        .solve_multiperiod(std::slice::from_ref(&second), 10)
+        .unwrap();
+    assert_eq!(
+        plans[1].allocations,
+        Matrix::try_from(vec![vec![0], vec![30]]).unwrap()
+    );
+    assert_eq!(plans[0].total_cost + plans[1].total_cost, 200);
+
+    let wider = TransportProblem::new();
+    assert!(matches!(
+        first.solve_multiperiod(&[wider], 2),
+        Err(TransportError::DimensionMismatch { .. })
+    ));
+}